@@ -1,21 +1,329 @@
 //! REST API Service for Spelling Bee Solver.
 //!
 //! Endpoints:
-//! - POST /solve: Accepts JSON config, returns word list (or enriched entries with validator).
-//! - POST /solve-stream: Like /solve, but streams SSE progress events during validation.
+//! - POST /solve: Accepts JSON config, returns `{"words": [...], "pangrams": [...], "count": N}`
+//!   (or enriched entries with validator).
+//! - GET /solve: Same as POST /solve, configured by `letters`, `present`, `min`, `max` query parameters.
+//! - POST /solve-stream: Like /solve, but streams SSE events as results arrive:
+//!   `{"word": {...}}` per confirmed word interleaved with `{"progress": {...}}`
+//!   (or `{"words": [...]}` chunks without a validator), then
+//!   `{"done": true, ...}` with the summary or total.
+//! - POST /solve-batch: Solves an array of /solve bodies; returns one /solve
+//!   result (or error object) per puzzle, in order.
+//! - POST /validate: Looks up one word with a validator; returns its entry
+//!   with `"found": true`, or `{"found": false}`. GET /validate takes the same
+//!   fields as query parameters.
+//! - POST /reload: Re-reads the dictionaries and swaps them in without a restart.
+//! - GET /dictionaries: Lists the available dictionaries and their word counts.
+//! - GET /metrics: Request, word, and latency counters as JSON.
 //! - GET /health: Status check.
+//!
+//! `/solve` accepts optional `offset` and `limit` (body fields or query
+//! parameters) to page through the sorted words; the response is then the
+//! same result object for the page, with `count` still the total number of
+//! words, plus the `offset` and `limit` used. With a validator, only the
+//! requested page is validated.
+//!
+//! The dictionary is read from `SBS_DICT`. A compiled copy is cached at
+//! `SBS_DICT_CACHE` (default: `$SBS_DICT.bin`) and reused while it is newer
+//! than the word list. When `SBS_RELOAD_TOKEN` is set, `/reload` requires a
+//! matching `X-Reload-Token` header.
+//!
+//! Validation in `/solve` runs off the request thread and stops after
+//! `SBS_VALIDATE_TIMEOUT` seconds (default: 30); the summary then holds the
+//! words confirmed so far and `"truncated": true`.
+//!
+//! Solving itself is abandoned after `SBS_SOLVE_TIMEOUT` seconds (default: 10)
+//! with a `503` and code `solve_timeout`, so one adversarial puzzle cannot
+//! hold a worker indefinitely.
+//!
+//! Errors are returned as `{"error": {"code": "...", "message": "..."}}` with a
+//! stable `code` (e.g. `missing_field`, `invalid_validator`, `solve_failed`).
+//!
+//! Responses are compressed according to the client's `Accept-Encoding`,
+//! except the SSE stream, which is sent uncompressed so events arrive promptly.
+//!
+//! Word sets for the most recent `SBS_SOLVE_CACHE_SIZE` puzzles (default:
+//! 256; 0 disables caching) are kept in memory per dictionary, so identical
+//! solve requests skip the search. A dictionary reload invalidates them.
+//!
+//! On SIGTERM or SIGINT the server stops accepting connections and gives
+//! in-flight requests up to `SBS_SHUTDOWN_GRACE` seconds (default: 30) to
+//! finish before exiting.
+//!
+//! Several named dictionaries can be served instead by setting `SBS_DICTS`
+//! (e.g. `en=data/en.txt,fr=data/fr.txt`, each cached at `<path>.bin`).
+//! Solve requests pick one with a `dictionary` field or query parameter;
+//! `SBS_DEFAULT_DICT` names the fallback (default: the first listed).
+//! Word lists are only chosen at startup: a request body carrying the
+//! config's `dictionaries` list is rejected with `invalid_config`.
 
 use actix_cors::Cors;
-use actix_web::{get, post, web, App, HttpResponse, HttpServer, Responder};
 #[cfg(feature = "validator")]
-use sbs::create_validator;
-use sbs::{Config, Dictionary, Solver};
+use actix_web::http::header::ContentEncoding;
+use actix_web::http::StatusCode;
+use actix_web::middleware::Compress;
+use actix_web::{get, post, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+#[cfg(feature = "validator")]
+use sbs::{create_validator, LookupOutcome, ValidatorKind};
+use sbs::{
+    CachingSolver, Config, Dictionary, SbsError, SolveResult, Solver, DEFAULT_SOLVE_CACHE_CAPACITY,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Default deadline for the trie search of one solve request.
+const DEFAULT_SOLVE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default time in-flight requests get to finish once shutdown begins.
+const DEFAULT_SHUTDOWN_GRACE: Duration = Duration::from_secs(30);
+
+/// Most puzzles accepted by one `/solve-batch` request.
+const MAX_BATCH_SIZE: usize = 100;
+
+/// Default overall deadline for validating one `/solve` request's words.
+#[cfg(feature = "validator")]
+const DEFAULT_VALIDATE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Counters reported by `GET /metrics`.
+#[derive(Default)]
+struct Metrics {
+    solve_requests: AtomicU64,
+    words_generated: AtomicU64,
+    solves: AtomicU64,
+    solve_micros: AtomicU64,
+    dictionary_words: AtomicUsize,
+}
+
+impl Metrics {
+    fn record_solve(&self, words: usize, started: Instant) {
+        let micros = started.elapsed().as_micros() as u64;
+        self.solves.fetch_add(1, Ordering::Relaxed);
+        self.solve_micros.fetch_add(micros, Ordering::Relaxed);
+        self.words_generated
+            .fetch_add(words as u64, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> serde_json::Value {
+        let solves = self.solves.load(Ordering::Relaxed);
+        let micros = self.solve_micros.load(Ordering::Relaxed);
+        let average_ms = if solves == 0 {
+            0.0
+        } else {
+            micros as f64 / solves as f64 / 1000.0
+        };
+        serde_json::json!({
+            "solve_requests": self.solve_requests.load(Ordering::Relaxed),
+            "words_generated": self.words_generated.load(Ordering::Relaxed),
+            "average_solve_ms": average_ms,
+            "dictionary_words": self.dictionary_words.load(Ordering::Relaxed),
+        })
+    }
+}
+
+/// Where a named dictionary is loaded from.
+#[derive(Debug, Clone, PartialEq)]
+struct DictionarySource {
+    name: String,
+    path: String,
+    cache_path: String,
+}
+
+/// Parse `SBS_DICTS`: comma-separated `name=path` pairs.
+fn parse_dictionary_sources(spec: &str) -> Result<Vec<DictionarySource>, String> {
+    let mut sources: Vec<DictionarySource> = Vec::new();
+    for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (name, path) = pair
+            .split_once('=')
+            .map(|(n, p)| (n.trim(), p.trim()))
+            .filter(|(n, p)| !n.is_empty() && !p.is_empty())
+            .ok_or_else(|| format!("Invalid dictionary entry '{}': expected name=path", pair))?;
+        if sources.iter().any(|s| s.name == name) {
+            return Err(format!("Duplicate dictionary name '{}'", name));
+        }
+        sources.push(DictionarySource {
+            name: name.to_string(),
+            path: path.to_string(),
+            cache_path: format!("{}.bin", path),
+        });
+    }
+    if sources.is_empty() {
+        return Err("No dictionaries configured".to_string());
+    }
+    Ok(sources)
+}
+
+/// A loaded dictionary with its word count, computed once at load time, and
+/// its own solve cache, so requests to other dictionaries never evict it.
+#[derive(Clone)]
+struct LoadedDictionary {
+    dictionary: Arc<Dictionary>,
+    word_count: usize,
+    /// Word sets of recent puzzles, so bursts of identical requests search once.
+    solve_cache: Arc<CachingSolver>,
+}
+
+impl LoadedDictionary {
+    fn new(dictionary: Dictionary, solve_cache_capacity: usize) -> Self {
+        Self {
+            word_count: dictionary.word_count(),
+            dictionary: Arc::new(dictionary),
+            solve_cache: Arc::new(CachingSolver::with_capacity(solve_cache_capacity)),
+        }
+    }
+}
 
 /// Shared application state
 struct AppState {
-    dictionary: Arc<Dictionary>,
+    dictionaries: RwLock<HashMap<String, LoadedDictionary>>,
+    sources: Vec<DictionarySource>,
+    default_dictionary: String,
+    reload_token: Option<String>,
+    metrics: Metrics,
+    solve_cache_capacity: usize,
+    solve_timeout: Duration,
+    #[cfg(feature = "validator")]
+    validate_timeout: Duration,
+}
+
+impl AppState {
+    fn new(
+        dictionaries: HashMap<String, Dictionary>,
+        sources: Vec<DictionarySource>,
+        default_dictionary: String,
+        reload_token: Option<String>,
+    ) -> Self {
+        let state = Self {
+            dictionaries: RwLock::new(HashMap::new()),
+            sources,
+            default_dictionary,
+            reload_token,
+            metrics: Metrics::default(),
+            solve_cache_capacity: DEFAULT_SOLVE_CACHE_CAPACITY,
+            solve_timeout: DEFAULT_SOLVE_TIMEOUT,
+            #[cfg(feature = "validator")]
+            validate_timeout: DEFAULT_VALIDATE_TIMEOUT,
+        };
+        state.replace_dictionaries(dictionaries);
+        state
+    }
+
+    /// Override how many puzzles the solve cache keeps; 0 disables it.
+    fn with_solve_cache_capacity(mut self, capacity: usize) -> Self {
+        self.solve_cache_capacity = capacity;
+        for loaded in self
+            .dictionaries
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .values_mut()
+        {
+            loaded.solve_cache = Arc::new(CachingSolver::with_capacity(capacity));
+        }
+        self
+    }
+
+    /// Override the deadline for solving one request.
+    fn with_solve_timeout(mut self, solve_timeout: Duration) -> Self {
+        self.solve_timeout = solve_timeout;
+        self
+    }
+
+    /// Override the overall validation deadline for `/solve`.
+    #[cfg(feature = "validator")]
+    fn with_validate_timeout(mut self, validate_timeout: Duration) -> Self {
+        self.validate_timeout = validate_timeout;
+        self
+    }
+
+    /// The named (or default) dictionary. Requests keep their snapshot across a reload.
+    fn dictionary(&self, name: Option<&str>) -> Result<LoadedDictionary, String> {
+        let name = name.unwrap_or(&self.default_dictionary);
+        let dictionaries = self.dictionaries.read().unwrap_or_else(|e| e.into_inner());
+        match dictionaries.get(name) {
+            Some(loaded) => Ok(loaded.clone()),
+            None => {
+                let mut names: Vec<&str> = dictionaries.keys().map(String::as_str).collect();
+                names.sort();
+                Err(format!(
+                    "Unknown dictionary '{}'; available: {}",
+                    name,
+                    names.join(", ")
+                ))
+            }
+        }
+    }
+
+    /// Word counts by dictionary name, sorted by name.
+    fn word_counts(&self) -> Vec<(String, usize)> {
+        let dictionaries = self.dictionaries.read().unwrap_or_else(|e| e.into_inner());
+        let mut counts: Vec<(String, usize)> = dictionaries
+            .iter()
+            .map(|(name, loaded)| (name.clone(), loaded.word_count))
+            .collect();
+        counts.sort();
+        counts
+    }
+
+    /// Atomically swap in a new set of dictionaries.
+    fn replace_dictionaries(&self, dictionaries: HashMap<String, Dictionary>) {
+        let loaded: HashMap<String, LoadedDictionary> = dictionaries
+            .into_iter()
+            .map(|(name, dictionary)| {
+                let loaded = LoadedDictionary::new(dictionary, self.solve_cache_capacity);
+                (name, loaded)
+            })
+            .collect();
+        let total: usize = loaded.values().map(|l| l.word_count).sum();
+        *self.dictionaries.write().unwrap_or_else(|e| e.into_inner()) = loaded;
+        self.metrics
+            .dictionary_words
+            .store(total, Ordering::Relaxed);
+    }
+}
+
+/// JSON body of an error: `{"error": {"code": ..., "message": ...}}`.
+fn error_body(code: &str, message: impl std::fmt::Display) -> serde_json::Value {
+    serde_json::json!({ "error": { "code": code, "message": message.to_string() } })
+}
+
+/// An error response with a stable machine-readable `code`.
+fn error_response(status: StatusCode, code: &str, message: impl std::fmt::Display) -> HttpResponse {
+    HttpResponse::build(status).json(error_body(code, message))
+}
+
+/// Status and code for a solver failure: bad configs are the client's fault.
+fn solve_error_kind(e: &SbsError) -> (StatusCode, &'static str) {
+    match e {
+        SbsError::ConfigError(_) => (StatusCode::BAD_REQUEST, "invalid_config"),
+        SbsError::Timeout(_) => (StatusCode::SERVICE_UNAVAILABLE, "solve_timeout"),
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, "solve_failed"),
+    }
+}
+
+/// Map a solver failure to a response.
+fn solve_error(e: SbsError) -> HttpResponse {
+    let (status, code) = solve_error_kind(&e);
+    error_response(status, code, e)
+}
+
+/// Report malformed JSON bodies in the structured error format.
+fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default().error_handler(|err, _req| {
+        let response = error_response(StatusCode::BAD_REQUEST, "invalid_json", &err);
+        actix_web::error::InternalError::from_response(err, response).into()
+    })
+}
+
+/// Report malformed query strings in the structured error format.
+fn query_config() -> web::QueryConfig {
+    web::QueryConfig::default().error_handler(|err, _req| {
+        let response = error_response(StatusCode::BAD_REQUEST, "invalid_query", &err);
+        actix_web::error::InternalError::from_response(err, response).into()
+    })
 }
 
 #[get("/health")]
@@ -23,13 +331,162 @@ async fn health() -> impl Responder {
     HttpResponse::Ok().body("OK")
 }
 
-#[post("/solve")]
-async fn solve_puzzle(data: web::Data<AppState>, config_json: web::Json<Config>) -> impl Responder {
-    let config = config_json.into_inner();
+#[get("/dictionaries")]
+async fn list_dictionaries(data: web::Data<AppState>) -> impl Responder {
+    let dictionaries: Vec<serde_json::Value> = data
+        .word_counts()
+        .into_iter()
+        .map(|(name, word_count)| serde_json::json!({"name": name, "word_count": word_count}))
+        .collect();
+    HttpResponse::Ok().json(serde_json::json!({
+        "default": data.default_dictionary,
+        "dictionaries": dictionaries,
+    }))
+}
+
+#[get("/metrics")]
+async fn report_metrics(data: web::Data<AppState>) -> impl Responder {
+    HttpResponse::Ok().json(data.metrics.snapshot())
+}
 
+/// JSON body accepted by `POST /solve` and `POST /solve-stream`: a [`Config`]
+/// plus the name of the dictionary to solve against.
+#[derive(Deserialize)]
+struct SolveRequest {
+    #[serde(default)]
+    dictionary: Option<String>,
+    #[serde(flatten)]
+    page: Page,
+    #[serde(flatten)]
+    config: Config,
+}
+
+/// Optional slice of the sorted words, taken after sorting so paging is stable.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+struct Page {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+impl Page {
+    fn is_requested(&self) -> bool {
+        self.offset.is_some() || self.limit.is_some()
+    }
+
+    /// The words in this page; empty when `offset` is past the end.
+    fn slice(&self, sorted: Vec<String>) -> Vec<String> {
+        sorted
+            .into_iter()
+            .skip(self.offset.unwrap_or(0))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    /// The paged response for `sorted`, the full sorted result list.
+    fn response(&self, solver: &Solver, sorted: Vec<String>) -> PagedResult {
+        let count = sorted.len();
+        PagedResult {
+            result: SolveResult {
+                count,
+                ..solver.result_from_words(self.slice(sorted))
+            },
+            offset: self.offset.unwrap_or(0),
+            limit: self.limit,
+        }
+    }
+}
+
+/// A [`SolveResult`] for one page: `words` and `pangrams` cover the page,
+/// while `count` is the total across all pages.
+#[derive(Serialize)]
+struct PagedResult {
+    #[serde(flatten)]
+    result: SolveResult,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+/// Query parameters accepted by `GET /solve`.
+#[derive(Deserialize)]
+struct SolveQuery {
+    dictionary: Option<String>,
+    letters: Option<String>,
+    present: Option<String>,
+    min: Option<usize>,
+    max: Option<usize>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+impl From<SolveQuery> for Config {
+    fn from(query: SolveQuery) -> Self {
+        let mut config = Config::new();
+        config.letters = query.letters;
+        config.present = query.present;
+        if query.min.is_some() {
+            config.minimal_word_length = query.min;
+        }
+        config.maximal_word_length = query.max;
+        config
+    }
+}
+
+/// Reject requests missing the letters every puzzle needs.
+fn missing_input(config: &Config) -> Option<&'static str> {
     if config.letters.is_none() {
-        return HttpResponse::BadRequest().body("Missing letters");
+        Some("Missing letters")
+    } else if config.present.is_none() {
+        Some("Missing present")
+    } else {
+        None
     }
+}
+
+/// Config fields that name files on the server, which requests may not set:
+/// the word lists are chosen at startup and picked by name with `dictionary`.
+fn server_only_field(config: &Config) -> Option<&'static str> {
+    config
+        .dictionaries
+        .is_some()
+        .then_some("dictionaries cannot be set per request; select a loaded dictionary by name")
+}
+
+/// Solve the puzzle (or reuse a cached result) and return the words in the
+/// requested sort order.
+fn solve_sorted(
+    cache: &CachingSolver,
+    config: &Config,
+    dictionary: &Dictionary,
+    timeout: Duration,
+) -> Result<Vec<String>, SbsError> {
+    cache.solve_sorted_with_deadline(config, dictionary, Instant::now() + timeout)
+}
+
+/// Shared body of the `/solve` handlers.
+async fn solve_response(
+    data: &AppState,
+    dictionary: Option<&str>,
+    config: Config,
+    page: Page,
+) -> HttpResponse {
+    data.metrics.solve_requests.fetch_add(1, Ordering::Relaxed);
+
+    if let Some(message) = missing_input(&config) {
+        return error_response(StatusCode::BAD_REQUEST, "missing_field", message);
+    }
+    if let Some(message) = server_only_field(&config) {
+        return error_response(StatusCode::BAD_REQUEST, "invalid_config", message);
+    }
+    if let Err(e) = config.validate() {
+        return error_response(StatusCode::BAD_REQUEST, "invalid_config", e);
+    }
+
+    let loaded = match data.dictionary(dictionary) {
+        Ok(d) => d,
+        Err(message) => {
+            return error_response(StatusCode::BAD_REQUEST, "unknown_dictionary", message)
+        }
+    };
 
     #[cfg(feature = "validator")]
     let validator_kind = config.validator.clone();
@@ -38,116 +495,273 @@ async fn solve_puzzle(data: web::Data<AppState>, config_json: web::Json<Config>)
     #[cfg(feature = "validator")]
     let validator_url = config.validator_url.clone();
 
-    let solver = Solver::new(config);
-
-    match solver.solve(&data.dictionary) {
-        Ok(words) => {
-            let mut sorted: Vec<String> = words.into_iter().collect();
-            sorted.sort();
+    let started = Instant::now();
+    match solve_sorted(
+        &loaded.solve_cache,
+        &config,
+        &loaded.dictionary,
+        data.solve_timeout,
+    ) {
+        Ok(sorted) => {
+            data.metrics.record_solve(sorted.len(), started);
 
             // If a validator is specified, enrich results with definitions and URLs
             #[cfg(feature = "validator")]
             if let Some(kind) = validator_kind {
+                let sorted = page.slice(sorted);
                 let validator =
                     match create_validator(&kind, api_key.as_deref(), validator_url.as_deref()) {
                         Ok(v) => v,
                         Err(e) => {
-                            return HttpResponse::BadRequest().body(e.to_string());
+                            return error_response(StatusCode::BAD_REQUEST, "invalid_validator", e);
                         }
                     };
 
-                let summary = validator.validate_words(&sorted);
+                let deadline = Instant::now() + data.validate_timeout;
+                let summary =
+                    match web::block(move || validator.validate_words_until(&sorted, deadline))
+                        .await
+                    {
+                        Ok(summary) => summary,
+                        Err(e) => {
+                            return error_response(
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                                "validation_failed",
+                                e,
+                            )
+                        }
+                    };
                 log::info!(
-                    "Validated: {} candidates, {} confirmed by {}",
+                    "Validated by {}: {} candidates, {} confirmed, {} unavailable{}",
+                    kind.display_name(),
                     summary.candidates,
                     summary.validated,
-                    kind.display_name()
+                    summary.unavailable,
+                    if summary.truncated {
+                        " (truncated at deadline)"
+                    } else {
+                        ""
+                    }
                 );
                 return HttpResponse::Ok().json(summary);
             }
 
-            HttpResponse::Ok().json(sorted)
+            let solver = Solver::from_config_ref(&config);
+            if page.is_requested() {
+                return HttpResponse::Ok().json(page.response(&solver, sorted));
+            }
+            HttpResponse::Ok().json(solver.result_from_words(sorted))
         }
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(e) => solve_error(e),
     }
 }
 
-/// SSE endpoint that streams validation progress.
-#[cfg(feature = "validator")]
-#[post("/solve-stream")]
-async fn solve_stream(data: web::Data<AppState>, config_json: web::Json<Config>) -> impl Responder {
-    use futures::stream;
-    use tokio::sync::mpsc;
+/// One `/solve-batch` entry: what `/solve` would return for `request`.
+fn batch_entry(data: &AppState, request: SolveRequest, deadline: Instant) -> serde_json::Value {
+    let SolveRequest {
+        dictionary,
+        config,
+        page,
+    } = request;
+    if let Some(message) = missing_input(&config) {
+        return error_body("missing_field", message);
+    }
+    if let Some(message) = server_only_field(&config) {
+        return error_body("invalid_config", message);
+    }
+    if let Err(e) = config.validate() {
+        return error_body("invalid_config", e);
+    }
+    let loaded = match data.dictionary(dictionary.as_deref()) {
+        Ok(d) => d,
+        Err(message) => return error_body("unknown_dictionary", message),
+    };
 
-    let config = config_json.into_inner();
+    let started = Instant::now();
+    match loaded
+        .solve_cache
+        .solve_sorted_with_deadline(&config, &loaded.dictionary, deadline)
+    {
+        Ok(sorted) => {
+            data.metrics.record_solve(sorted.len(), started);
+            let solver = Solver::from_config_ref(&config);
+            if page.is_requested() {
+                serde_json::json!(page.response(&solver, sorted))
+            } else {
+                serde_json::json!(solver.result_from_words(sorted))
+            }
+        }
+        Err(e) => error_body(solve_error_kind(&e).1, e),
+    }
+}
 
-    if config.letters.is_none() {
-        return HttpResponse::BadRequest().body("Missing letters");
+/// Solve several puzzles in one request. Failures are reported per puzzle, so
+/// one bad config does not fail the batch; `SBS_SOLVE_TIMEOUT` bounds the
+/// whole batch.
+#[post("/solve-batch")]
+async fn solve_batch(
+    data: web::Data<AppState>,
+    requests: web::Json<Vec<SolveRequest>>,
+) -> impl Responder {
+    let requests = requests.into_inner();
+    if requests.len() > MAX_BATCH_SIZE {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "batch_too_large",
+            format!(
+                "Batch of {} puzzles exceeds the limit of {}",
+                requests.len(),
+                MAX_BATCH_SIZE
+            ),
+        );
     }
+    data.metrics
+        .solve_requests
+        .fetch_add(requests.len() as u64, Ordering::Relaxed);
+
+    let deadline = Instant::now() + data.solve_timeout;
+    let entries: Vec<serde_json::Value> = requests
+        .into_iter()
+        .map(|request| batch_entry(&data, request, deadline))
+        .collect();
+    HttpResponse::Ok().json(entries)
+}
+
+#[post("/solve")]
+async fn solve_puzzle(
+    data: web::Data<AppState>,
+    request: web::Json<SolveRequest>,
+) -> impl Responder {
+    let request = request.into_inner();
+    solve_response(
+        &data,
+        request.dictionary.as_deref(),
+        request.config,
+        request.page,
+    )
+    .await
+}
+
+#[get("/solve")]
+async fn solve_puzzle_query(
+    data: web::Data<AppState>,
+    query: web::Query<SolveQuery>,
+) -> impl Responder {
+    let query = query.into_inner();
+    let dictionary = query.dictionary.clone();
+    let page = Page {
+        offset: query.offset,
+        limit: query.limit,
+    };
+    solve_response(&data, dictionary.as_deref(), query.into(), page).await
+}
+
+/// Words per `{"words": [...]}` event when `/solve-stream` has no validator.
+#[cfg(feature = "validator")]
+const STREAM_CHUNK_SIZE: usize = 100;
+
+/// Solve `config` and send each SSE event to `tx` as soon as it is known.
+/// Send errors are ignored: they only mean the client has gone away.
+#[cfg(feature = "validator")]
+fn stream_solve_events(
+    cache: &CachingSolver,
+    config: Config,
+    dictionary: &Dictionary,
+    solve_timeout: Duration,
+    tx: &tokio::sync::mpsc::UnboundedSender<String>,
+) {
+    let send = |event: serde_json::Value| {
+        let _ = tx.send(format!("data: {}\n\n", event));
+    };
 
     let validator_kind = config.validator.clone();
     let api_key = config.api_key.clone();
     let validator_url = config.validator_url.clone();
-    let dictionary = data.dictionary.clone();
 
-    let (tx, rx) = mpsc::unbounded_channel::<String>();
+    let words = match solve_sorted(cache, &config, dictionary, solve_timeout) {
+        Ok(words) => words,
+        Err(e) => {
+            let (_, code) = solve_error_kind(&e);
+            send(error_body(code, e));
+            return;
+        }
+    };
 
-    // Run solving and validation in a blocking thread
-    std::thread::spawn(move || {
-        let solver = Solver::new(config);
+    let Some(kind) = validator_kind else {
+        for chunk in words.chunks(STREAM_CHUNK_SIZE) {
+            send(serde_json::json!({"words": chunk}));
+        }
+        send(serde_json::json!({"done": true, "total": words.len()}));
+        return;
+    };
 
-        let words = match solver.solve(&dictionary) {
-            Ok(words) => {
-                let mut sorted: Vec<String> = words.into_iter().collect();
-                sorted.sort();
-                sorted
-            }
-            Err(e) => {
-                let _ = tx.send(format!(
-                    "data: {}\n\n",
-                    serde_json::json!({"error": e.to_string()})
-                ));
-                return;
-            }
-        };
+    let validator = match create_validator(&kind, api_key.as_deref(), validator_url.as_deref()) {
+        Ok(v) => v,
+        Err(e) => {
+            send(error_body("invalid_validator", e));
+            return;
+        }
+    };
 
-        if let Some(kind) = validator_kind {
-            let validator =
-                match create_validator(&kind, api_key.as_deref(), validator_url.as_deref()) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        let _ = tx.send(format!(
-                            "data: {}\n\n",
-                            serde_json::json!({"error": e.to_string()})
-                        ));
-                        return;
-                    }
-                };
+    let summary = validator.validate_words_streaming(
+        &words,
+        &|done, total| send(serde_json::json!({"progress": {"done": done, "total": total}})),
+        &|entry| send(serde_json::json!({"word": entry})),
+    );
 
-            let summary = validator.validate_words_with_progress(&words, &|done, total| {
-                let _ = tx.send(format!(
-                    "data: {}\n\n",
-                    serde_json::json!({"progress": {"done": done, "total": total}})
-                ));
-            });
-
-            log::info!(
-                "Validated: {} candidates, {} confirmed by {}",
-                summary.candidates,
-                summary.validated,
-                kind.display_name()
-            );
+    log::info!(
+        "Validated: {} candidates, {} confirmed by {}",
+        summary.candidates,
+        summary.validated,
+        kind.display_name()
+    );
 
-            let _ = tx.send(format!(
-                "data: {}\n\n",
-                serde_json::json!({"result": summary})
-            ));
-        } else {
-            let _ = tx.send(format!(
-                "data: {}\n\n",
-                serde_json::json!({"result": words})
-            ));
+    send(serde_json::json!({"done": true, "summary": summary}));
+}
+
+/// SSE endpoint that streams words as they are found and validated.
+#[cfg(feature = "validator")]
+#[post("/solve-stream")]
+async fn solve_stream(
+    data: web::Data<AppState>,
+    request: web::Json<SolveRequest>,
+) -> impl Responder {
+    use futures::stream;
+    use tokio::sync::mpsc;
+
+    let SolveRequest {
+        dictionary, config, ..
+    } = request.into_inner();
+
+    if let Some(message) = missing_input(&config) {
+        return error_response(StatusCode::BAD_REQUEST, "missing_field", message);
+    }
+    if let Some(message) = server_only_field(&config) {
+        return error_response(StatusCode::BAD_REQUEST, "invalid_config", message);
+    }
+    if let Err(e) = config.validate() {
+        return error_response(StatusCode::BAD_REQUEST, "invalid_config", e);
+    }
+
+    let loaded = match data.dictionary(dictionary.as_deref()) {
+        Ok(d) => d,
+        Err(message) => {
+            return error_response(StatusCode::BAD_REQUEST, "unknown_dictionary", message)
         }
+    };
+
+    let state = data.clone();
+    let (tx, rx) = mpsc::unbounded_channel::<String>();
+
+    // Run solving and validation in a blocking thread
+    std::thread::spawn(move || {
+        stream_solve_events(
+            &loaded.solve_cache,
+            config,
+            &loaded.dictionary,
+            state.solve_timeout,
+            &tx,
+        )
     });
 
     let event_stream = stream::unfold(rx, |mut rx| async move {
@@ -156,46 +770,984 @@ async fn solve_stream(data: web::Data<AppState>, config_json: web::Json<Config>)
             .map(|msg| (Ok::<_, actix_web::Error>(web::Bytes::from(msg)), rx))
     });
 
+    // Opt out of compression: an encoder buffers events until its block fills.
     HttpResponse::Ok()
         .insert_header(("Content-Type", "text/event-stream"))
         .insert_header(("Cache-Control", "no-cache"))
+        .insert_header(ContentEncoding::Identity)
         .streaming(event_stream)
 }
 
+/// Body (or query) accepted by `/validate`.
+#[cfg(feature = "validator")]
+#[derive(Deserialize)]
+struct ValidateRequest {
+    word: Option<String>,
+    validator: Option<String>,
+    #[serde(rename = "api-key")]
+    api_key: Option<String>,
+    #[serde(rename = "validator-url")]
+    validator_url: Option<String>,
+}
+
+/// Shared body of the `/validate` handlers: one lookup, no solving.
+#[cfg(feature = "validator")]
+async fn validate_response(request: ValidateRequest) -> HttpResponse {
+    let Some(word) = request.word.filter(|w| !w.trim().is_empty()) else {
+        return error_response(StatusCode::BAD_REQUEST, "missing_field", "Missing word");
+    };
+    let Some(name) = request.validator else {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "missing_field",
+            "Missing validator",
+        );
+    };
+    let validator = match name.parse::<ValidatorKind>().and_then(|kind| {
+        create_validator(
+            &kind,
+            request.api_key.as_deref(),
+            request.validator_url.as_deref(),
+        )
+    }) {
+        Ok(v) => v,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, "invalid_validator", e),
+    };
+
+    let word = word.trim().to_lowercase();
+    match web::block(move || validator.check(&word)).await {
+        Ok(LookupOutcome::Found(entry)) => {
+            let mut body = serde_json::json!(entry);
+            body["found"] = true.into();
+            HttpResponse::Ok().json(body)
+        }
+        Ok(LookupOutcome::NotFound) => HttpResponse::Ok().json(serde_json::json!({"found": false})),
+        Ok(LookupOutcome::Unavailable(reason)) => {
+            error_response(StatusCode::BAD_GATEWAY, "validator_unavailable", reason)
+        }
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, "validation_failed", e),
+    }
+}
+
+#[cfg(feature = "validator")]
+#[post("/validate")]
+async fn validate_word(request: web::Json<ValidateRequest>) -> impl Responder {
+    validate_response(request.into_inner()).await
+}
+
+#[cfg(feature = "validator")]
+#[get("/validate")]
+async fn validate_word_query(query: web::Query<ValidateRequest>) -> impl Responder {
+    validate_response(query.into_inner()).await
+}
+
+/// Re-read the dictionaries and swap them in. On failure the old ones stay live.
+#[post("/reload")]
+async fn reload(req: HttpRequest, data: web::Data<AppState>) -> impl Responder {
+    if let Some(expected) = &data.reload_token {
+        let supplied = req
+            .headers()
+            .get("X-Reload-Token")
+            .and_then(|v| v.to_str().ok());
+        if supplied != Some(expected.as_str()) {
+            return error_response(
+                StatusCode::UNAUTHORIZED,
+                "unauthorized",
+                "Invalid reload token",
+            );
+        }
+    }
+
+    let state = data.clone();
+    let loaded = web::block(move || load_dictionaries(&state.sources)).await;
+    match loaded {
+        Ok(Ok(dictionaries)) => {
+            data.replace_dictionaries(dictionaries);
+            let counts = data.word_counts();
+            let word_count: usize = counts.iter().map(|(_, count)| count).sum();
+            log::info!("Reloaded dictionaries: {} words", word_count);
+            HttpResponse::Ok().json(serde_json::json!({
+                "word_count": word_count,
+                "dictionaries": counts.into_iter().collect::<HashMap<_, _>>(),
+            }))
+        }
+        Ok(Err(e)) => {
+            log::error!("Failed to reload dictionary: {}", e);
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, "reload_failed", e)
+        }
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, "reload_failed", e),
+    }
+}
+
+/// Load the compiled dictionary cache if it is newer than the word list,
+/// otherwise parse the word list and refresh the cache.
+fn load_dictionary(dict_path: &str, cache_path: &str) -> Result<Dictionary, sbs::SbsError> {
+    let modified = |p: &str| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+
+    if let (Some(cache_time), Some(source_time)) = (modified(cache_path), modified(dict_path)) {
+        if cache_time >= source_time {
+            log::info!("Loading compiled dictionary from: {}", cache_path);
+            match Dictionary::load_compiled(cache_path) {
+                Ok(d) => return Ok(d),
+                Err(e) => log::warn!("Ignoring compiled dictionary: {}", e),
+            }
+        }
+    }
+
+    log::info!("Loading dictionary from: {}", dict_path);
+    let dictionary = Dictionary::from_file(dict_path)?;
+    match dictionary.save_compiled(cache_path) {
+        Ok(()) => log::info!("Wrote compiled dictionary to: {}", cache_path),
+        Err(e) => log::warn!("Could not write compiled dictionary: {}", e),
+    }
+    Ok(dictionary)
+}
+
+/// Load every configured dictionary, failing if any one of them fails.
+fn load_dictionaries(
+    sources: &[DictionarySource],
+) -> Result<HashMap<String, Dictionary>, sbs::SbsError> {
+    sources
+        .iter()
+        .map(|source| {
+            load_dictionary(&source.path, &source.cache_path)
+                .map(|dictionary| (source.name.clone(), dictionary))
+        })
+        .collect()
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
 
-    let dict_path = env::var("SBS_DICT").unwrap_or_else(|_| "data/dictionary.txt".to_string());
+    let sources = match env::var("SBS_DICTS") {
+        Ok(spec) => match parse_dictionary_sources(&spec) {
+            Ok(sources) => sources,
+            Err(e) => {
+                log::error!("Invalid SBS_DICTS: {}", e);
+                std::process::exit(1);
+            }
+        },
+        Err(_) => {
+            let path = env::var("SBS_DICT").unwrap_or_else(|_| "data/dictionary.txt".to_string());
+            let cache_path = env::var("SBS_DICT_CACHE").unwrap_or_else(|_| format!("{}.bin", path));
+            vec![DictionarySource {
+                name: "default".to_string(),
+                path,
+                cache_path,
+            }]
+        }
+    };
 
-    log::info!("Loading dictionary from: {}", dict_path);
-    let dictionary = match Dictionary::from_file(&dict_path) {
-        Ok(d) => Arc::new(d),
+    let default_dictionary =
+        env::var("SBS_DEFAULT_DICT").unwrap_or_else(|_| sources[0].name.clone());
+    if !sources.iter().any(|s| s.name == default_dictionary) {
+        log::error!(
+            "Default dictionary '{}' is not configured",
+            default_dictionary
+        );
+        std::process::exit(1);
+    }
+
+    let dictionaries = match load_dictionaries(&sources) {
+        Ok(d) => d,
         Err(e) => {
             log::error!("Failed to load dictionary: {}", e);
             std::process::exit(1);
         }
     };
 
+    let state = AppState::new(
+        dictionaries,
+        sources,
+        default_dictionary,
+        env::var("SBS_RELOAD_TOKEN").ok(),
+    );
+    let state = match env::var("SBS_SOLVE_TIMEOUT").map(|v| v.parse::<u64>()) {
+        Ok(Ok(seconds)) => state.with_solve_timeout(Duration::from_secs(seconds)),
+        Ok(Err(e)) => {
+            log::error!("Invalid SBS_SOLVE_TIMEOUT: {}", e);
+            std::process::exit(1);
+        }
+        Err(_) => state,
+    };
+    #[cfg(feature = "validator")]
+    let state = match env::var("SBS_VALIDATE_TIMEOUT").map(|v| v.parse::<u64>()) {
+        Ok(Ok(seconds)) => state.with_validate_timeout(Duration::from_secs(seconds)),
+        Ok(Err(e)) => {
+            log::error!("Invalid SBS_VALIDATE_TIMEOUT: {}", e);
+            std::process::exit(1);
+        }
+        Err(_) => state,
+    };
+    let state = match env::var("SBS_SOLVE_CACHE_SIZE").map(|v| v.parse::<usize>()) {
+        Ok(Ok(capacity)) => state.with_solve_cache_capacity(capacity),
+        Ok(Err(e)) => {
+            log::error!("Invalid SBS_SOLVE_CACHE_SIZE: {}", e);
+            std::process::exit(1);
+        }
+        Err(_) => state,
+    };
+    let shutdown_grace = match env::var("SBS_SHUTDOWN_GRACE").map(|v| v.parse::<u64>()) {
+        Ok(Ok(seconds)) => Duration::from_secs(seconds),
+        Ok(Err(e)) => {
+            log::error!("Invalid SBS_SHUTDOWN_GRACE: {}", e);
+            std::process::exit(1);
+        }
+        Err(_) => DEFAULT_SHUTDOWN_GRACE,
+    };
+    let state = web::Data::new(state);
+
     log::info!("Starting server at http://0.0.0.0:8080");
 
-    HttpServer::new(move || {
+    let server = HttpServer::new(move || {
         let mut app = App::new()
             .wrap(Cors::permissive())
-            .app_data(web::Data::new(AppState {
-                dictionary: dictionary.clone(),
-            }))
+            .wrap(Compress::default())
+            .app_data(state.clone())
+            .app_data(json_config())
+            .app_data(query_config())
             .service(health)
-            .service(solve_puzzle);
+            .service(report_metrics)
+            .service(list_dictionaries)
+            .service(reload)
+            .service(solve_puzzle)
+            .service(solve_puzzle_query)
+            .service(solve_batch);
 
         #[cfg(feature = "validator")]
         {
-            app = app.service(solve_stream);
+            app = app
+                .service(solve_stream)
+                .service(validate_word)
+                .service(validate_word_query);
         }
 
         app
     })
     .bind(("0.0.0.0", 8080))?
-    .run()
-    .await
+    .shutdown_timeout(shutdown_grace.as_secs())
+    .disable_signals()
+    .run();
+
+    let handle = server.handle();
+    actix_web::rt::spawn(async move {
+        let signal = shutdown_signal().await;
+        log::info!(
+            "Received {}; draining in-flight requests (grace period {}s)",
+            signal,
+            shutdown_grace.as_secs()
+        );
+        handle.stop(true).await;
+    });
+
+    server.await?;
+    log::info!("Shutdown complete");
+    Ok(())
+}
+
+/// Wait for SIGINT or, on Unix, SIGTERM and return the signal's name.
+async fn shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use actix_web::rt::signal::unix::{signal, SignalKind};
+        use futures::future::{self, Either};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                let interrupt = Box::pin(actix_web::rt::signal::ctrl_c());
+                let terminate = Box::pin(terminate.recv());
+                return match future::select(interrupt, terminate).await {
+                    Either::Left(_) => "SIGINT",
+                    Either::Right(_) => "SIGTERM",
+                };
+            }
+            Err(e) => log::warn!("Cannot listen for SIGTERM: {}", e),
+        }
+    }
+    let _ = actix_web::rt::signal::ctrl_c().await;
+    "SIGINT"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test as actix_test;
+
+    fn test_state() -> web::Data<AppState> {
+        let mut dictionaries = HashMap::new();
+        dictionaries.insert(
+            "en".to_string(),
+            Dictionary::from_words(&["apple", "pale", "leap", "plea", "peal"]),
+        );
+        dictionaries.insert(
+            "fr".to_string(),
+            Dictionary::from_words(&["pelle", "appel"]),
+        );
+        web::Data::new(AppState::new(
+            dictionaries,
+            Vec::new(),
+            "en".to_string(),
+            None,
+        ))
+    }
+
+    #[actix_web::test]
+    async fn test_metrics_count_solve_requests() {
+        let state = test_state();
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(state.clone())
+                .service(solve_puzzle)
+                .service(report_metrics),
+        )
+        .await;
+
+        for _ in 0..2 {
+            let req = actix_test::TestRequest::post()
+                .uri("/solve")
+                .set_json(serde_json::json!({"letters": "aelp", "present": "a"}))
+                .to_request();
+            let resp = actix_test::call_service(&app, req).await;
+            assert!(resp.status().is_success());
+        }
+
+        let req = actix_test::TestRequest::get().uri("/metrics").to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["solve_requests"], 2);
+        assert_eq!(body["words_generated"], 10, "all five words, twice");
+        assert_eq!(body["dictionary_words"], 7);
+        assert_eq!(
+            state.dictionary(None).unwrap().solve_cache.len(),
+            1,
+            "the second solve is a cache hit"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_dictionaries_keep_separate_solve_caches() {
+        let state = test_state();
+        let app =
+            actix_test::init_service(App::new().app_data(state.clone()).service(solve_puzzle))
+                .await;
+
+        for dictionary in ["en", "fr", "en", "fr"] {
+            let req = actix_test::TestRequest::post()
+                .uri("/solve")
+                .set_json(serde_json::json!({
+                    "letters": "aelp", "present": "a", "dictionary": dictionary
+                }))
+                .to_request();
+            let resp = actix_test::call_service(&app, req).await;
+            assert!(resp.status().is_success());
+        }
+
+        for dictionary in ["en", "fr"] {
+            assert_eq!(
+                state
+                    .dictionary(Some(dictionary))
+                    .unwrap()
+                    .solve_cache
+                    .len(),
+                1,
+                "{} keeps its entry while the other is queried",
+                dictionary
+            );
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_solve_returns_shared_result_shape() {
+        let app =
+            actix_test::init_service(App::new().app_data(test_state()).service(solve_puzzle)).await;
+
+        let req = actix_test::TestRequest::post()
+            .uri("/solve")
+            .set_json(serde_json::json!({"letters": "aelpx", "present": "a"}))
+            .to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+
+        let expected = SolveResult {
+            words: ["apple", "leap", "pale", "peal", "plea"]
+                .map(String::from)
+                .to_vec(),
+            pangrams: Vec::new(),
+            count: 5,
+            bonus: Vec::new(),
+        };
+        assert_eq!(body, serde_json::to_value(expected).unwrap());
+    }
+
+    #[actix_web::test]
+    async fn test_solve_batch_reports_each_puzzle() {
+        let app =
+            actix_test::init_service(App::new().app_data(test_state()).service(solve_batch)).await;
+
+        let req = actix_test::TestRequest::post()
+            .uri("/solve-batch")
+            .set_json(serde_json::json!([
+                {"letters": "aelp", "present": "a"},
+                {"letters": "aelp", "present": "a", "dictionary": "fr"},
+                {"letters": "aelp"},
+                {"letters": "aelp", "present": "a", "dictionary": "de"},
+            ]))
+            .to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(
+            body[0]["words"],
+            serde_json::json!(["apple", "leap", "pale", "peal", "plea"])
+        );
+        assert_eq!(body[0]["count"], 5);
+        assert_eq!(body[1]["words"], serde_json::json!(["appel"]));
+        assert_eq!(body[2]["error"]["code"], "missing_field");
+        assert_eq!(body[3]["error"]["code"], "unknown_dictionary");
+
+        let oversized = vec![serde_json::json!({"letters": "aelp", "present": "a"}); 101];
+        let req = actix_test::TestRequest::post()
+            .uri("/solve-batch")
+            .set_json(oversized)
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+    }
+
+    #[cfg(feature = "validator")]
+    #[actix_web::test]
+    async fn test_validate_single_word() {
+        use std::io::Write;
+
+        let mut words = tempfile::NamedTempFile::new().unwrap();
+        writeln!(words, "apple\nleap").unwrap();
+        let path = words.path().to_str().unwrap().to_string();
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(json_config())
+                .service(validate_word)
+                .service(validate_word_query),
+        )
+        .await;
+        let validate = |word: &str| {
+            actix_test::TestRequest::post()
+                .uri("/validate")
+                .set_json(serde_json::json!({
+                    "word": word, "validator": "offline", "validator-url": path
+                }))
+                .to_request()
+        };
+
+        let body: serde_json::Value =
+            actix_test::call_and_read_body_json(&app, validate("Apple")).await;
+        assert_eq!(body["found"], true);
+        assert_eq!(body["word"], "apple");
+
+        let body: serde_json::Value =
+            actix_test::call_and_read_body_json(&app, validate("zebra")).await;
+        assert_eq!(body, serde_json::json!({"found": false}));
+
+        let req = actix_test::TestRequest::get()
+            .uri("/validate?validator=offline")
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["error"]["code"], "missing_field");
+
+        let req = actix_test::TestRequest::get()
+            .uri("/validate?word=apple")
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400, "no validator configured");
+
+        let req = actix_test::TestRequest::get()
+            .uri("/validate?word=apple&validator=offline")
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(
+            body["error"]["code"], "invalid_validator",
+            "offline needs a path"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_solve_pagination() {
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(test_state())
+                .service(solve_puzzle)
+                .service(solve_puzzle_query),
+        )
+        .await;
+        let page = |offset: usize, limit: usize| {
+            actix_test::TestRequest::post()
+                .uri("/solve")
+                .set_json(serde_json::json!({
+                    "letters": "aelp", "present": "a", "offset": offset, "limit": limit
+                }))
+                .to_request()
+        };
+
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, page(0, 2)).await;
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "words": ["apple", "leap"],
+                "pangrams": ["apple", "leap"],
+                "count": 5,
+                "offset": 0,
+                "limit": 2
+            })
+        );
+        let result: SolveResult = serde_json::from_value(body).unwrap();
+        assert_eq!(result.count, 5, "pages parse as the shared result shape");
+
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, page(2, 2)).await;
+        assert_eq!(body["words"], serde_json::json!(["pale", "peal"]));
+
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, page(4, 2)).await;
+        assert_eq!(
+            body["words"],
+            serde_json::json!(["plea"]),
+            "short last page"
+        );
+
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, page(9, 2)).await;
+        assert_eq!(body["words"], serde_json::json!([]));
+        assert_eq!(body["count"], 5);
+
+        let req = actix_test::TestRequest::get()
+            .uri("/solve?letters=aelp&present=a&offset=3")
+            .to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["words"], serde_json::json!(["peal", "plea"]));
+        assert_eq!(body["limit"], serde_json::Value::Null);
+    }
+
+    #[actix_web::test]
+    async fn test_solve_timeout_returns_503() {
+        let mut dictionaries = HashMap::new();
+        dictionaries.insert("en".to_string(), Dictionary::from_words(&["pale", "leap"]));
+        let state = AppState::new(dictionaries, Vec::new(), "en".to_string(), None)
+            .with_solve_timeout(Duration::ZERO);
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(state))
+                .service(solve_puzzle),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::post()
+            .uri("/solve")
+            .set_json(serde_json::json!({"letters": "aelp", "present": "a"}))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 503);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["error"]["code"], "solve_timeout");
+    }
+
+    #[actix_web::test]
+    async fn test_solve_selects_named_dictionary() {
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(test_state())
+                .service(solve_puzzle)
+                .service(solve_puzzle_query)
+                .service(list_dictionaries),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::post()
+            .uri("/solve")
+            .set_json(serde_json::json!({"letters": "aelp", "present": "a", "dictionary": "fr"}))
+            .to_request();
+        let result: SolveResult = actix_test::call_and_read_body_json(&app, req).await;
+        assert_eq!(result.words, vec!["appel"]);
+
+        let req = actix_test::TestRequest::get()
+            .uri("/solve?letters=aelp&present=a")
+            .to_request();
+        let result: SolveResult = actix_test::call_and_read_body_json(&app, req).await;
+        assert_eq!(result.count, 5, "default dictionary");
+
+        let req = actix_test::TestRequest::get()
+            .uri("/solve?letters=aelp&present=a&dictionary=de")
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["error"]["code"], "unknown_dictionary");
+        assert_eq!(
+            body["error"]["message"],
+            "Unknown dictionary 'de'; available: en, fr"
+        );
+
+        let req = actix_test::TestRequest::get()
+            .uri("/dictionaries")
+            .to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["default"], "en");
+        assert_eq!(body["dictionaries"][1]["name"], "fr");
+        assert_eq!(body["dictionaries"][1]["word_count"], 2);
+    }
+
+    #[actix_web::test]
+    async fn test_solve_response_is_gzip_compressed() {
+        use std::io::Read;
+
+        let words: Vec<String> = (0..2000)
+            .map(|i| format!("ab{:b}", i).replace('0', "a").replace('1', "b"))
+            .collect();
+        let refs: Vec<&str> = words.iter().map(String::as_str).collect();
+        let mut dictionaries = HashMap::new();
+        dictionaries.insert("en".to_string(), Dictionary::from_words(&refs));
+        let state = web::Data::new(AppState::new(
+            dictionaries,
+            Vec::new(),
+            "en".to_string(),
+            None,
+        ));
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(Compress::default())
+                .app_data(state)
+                .service(solve_puzzle),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::post()
+            .uri("/solve")
+            .insert_header(("Accept-Encoding", "gzip"))
+            .set_json(serde_json::json!({"letters": "ab", "present": "a"}))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.headers().get("Content-Encoding").unwrap(), "gzip");
+
+        let compressed = actix_test::read_body(resp).await;
+        let mut json = String::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_string(&mut json)
+            .unwrap();
+        assert!(compressed.len() < json.len());
+        let solved: SolveResult = serde_json::from_str(&json).unwrap();
+        assert!(solved.count > 1000);
+    }
+
+    #[cfg(feature = "validator")]
+    #[actix_web::test]
+    async fn test_solve_stream_is_not_compressed() {
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(Compress::default())
+                .app_data(test_state())
+                .service(solve_stream),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::post()
+            .uri("/solve-stream")
+            .insert_header(("Accept-Encoding", "gzip"))
+            .set_json(serde_json::json!({"letters": "aelp", "present": "a"}))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert!(resp
+            .headers()
+            .get("Content-Encoding")
+            .is_none_or(|v| v == "identity"));
+        let body = actix_test::read_body(resp).await;
+        assert!(body.starts_with(b"data: "));
+    }
+
+    #[cfg(feature = "validator")]
+    fn collect_stream_events(config: Config, dictionary: &Dictionary) -> Vec<serde_json::Value> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        stream_solve_events(
+            &CachingSolver::new(),
+            config,
+            dictionary,
+            DEFAULT_SOLVE_TIMEOUT,
+            &tx,
+        );
+        let mut events = Vec::new();
+        while let Ok(message) = rx.try_recv() {
+            let data = message
+                .strip_prefix("data: ")
+                .and_then(|m| m.strip_suffix("\n\n"))
+                .expect("SSE data frame");
+            events.push(serde_json::from_str(data).unwrap());
+        }
+        events
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn test_solve_stream_interleaves_words_and_progress() {
+        use std::io::Write;
+
+        let mut known = tempfile::NamedTempFile::new().unwrap();
+        writeln!(known, "leap\npale").unwrap();
+        let mut config = Config::new().with_letters("aelp").with_present("a");
+        config.validator = Some(ValidatorKind::Offline);
+        config.validator_url = Some(known.path().to_str().unwrap().to_string());
+        let dictionary = Dictionary::from_words(&["apple", "leap", "pale", "plea"]);
+
+        let events = collect_stream_events(config, &dictionary);
+
+        // Sorted candidates: apple, leap, pale, plea.
+        let kinds: Vec<String> = events
+            .iter()
+            .map(
+                |e| match e.as_object().unwrap().keys().next().unwrap().as_str() {
+                    "word" => format!("word:{}", e["word"]["word"].as_str().unwrap()),
+                    "progress" => format!("progress:{}", e["progress"]["done"]),
+                    other => other.to_string(),
+                },
+            )
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "progress:1",
+                "word:leap",
+                "progress:2",
+                "word:pale",
+                "progress:3",
+                "progress:4",
+                "done",
+            ]
+        );
+        let done = events.last().unwrap();
+        assert_eq!(done["done"], true);
+        assert_eq!(done["summary"]["candidates"], 4);
+        assert_eq!(done["summary"]["validated"], 2);
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn test_solve_stream_chunks_unvalidated_words() {
+        let words: Vec<String> = (0..STREAM_CHUNK_SIZE + 5)
+            .map(|i| format!("{:08b}", i).replace('0', "a").replace('1', "b"))
+            .collect();
+        let refs: Vec<&str> = words.iter().map(String::as_str).collect();
+        let dictionary = Dictionary::from_words(&refs);
+        let config = Config::new().with_letters("ab").with_present("a");
+
+        let events = collect_stream_events(config, &dictionary);
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0]["words"].as_array().unwrap().len(),
+            STREAM_CHUNK_SIZE
+        );
+        assert_eq!(events[1]["words"].as_array().unwrap().len(), 5);
+        assert_eq!(
+            events[2],
+            serde_json::json!({"done": true, "total": STREAM_CHUNK_SIZE + 5})
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_errors_are_structured_json() {
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(test_state())
+                .app_data(json_config())
+                .service(solve_puzzle),
+        )
+        .await;
+
+        let cases = [
+            (r#"{"present": "a"}"#, 400, "missing_field"),
+            (r#"{"letters": "aelp"}"#, 400, "missing_field"),
+            (r#"{"letters": "#, 400, "invalid_json"),
+            (
+                r#"{"letters": "aelp", "present": "z"}"#,
+                400,
+                "invalid_config",
+            ),
+            (
+                r#"{"letters": "aelp", "present": "a", "pattern": "("}"#,
+                400,
+                "invalid_config",
+            ),
+            (
+                r#"{"letters": "aelp", "present": "a", "pattern": "p_[e"}"#,
+                400,
+                "invalid_config",
+            ),
+            (
+                r#"{"letters": "aelp", "present": "a", "dictionaries": ["/etc/words"]}"#,
+                400,
+                "invalid_config",
+            ),
+        ];
+        for (payload, status, code) in cases {
+            let req = actix_test::TestRequest::post()
+                .uri("/solve")
+                .insert_header(("Content-Type", "application/json"))
+                .set_payload(payload)
+                .to_request();
+            let resp = actix_test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status, "{}", payload);
+            let body: serde_json::Value = actix_test::read_body_json(resp).await;
+            assert_eq!(body["error"]["code"], code, "{}", payload);
+            assert!(body["error"]["message"].is_string());
+        }
+    }
+
+    #[cfg(feature = "validator")]
+    #[actix_web::test]
+    async fn test_bad_validator_error_code() {
+        let app =
+            actix_test::init_service(App::new().app_data(test_state()).service(solve_puzzle)).await;
+
+        let req = actix_test::TestRequest::post()
+            .uri("/solve")
+            .set_json(serde_json::json!({
+                "letters": "aelp",
+                "present": "a",
+                "validator": "merriam-webster"
+            }))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["error"]["code"], "invalid_validator");
+    }
+
+    /// State serving one dictionary read from `path`, as `main` loads it.
+    fn file_state(path: &std::path::Path, reload_token: Option<&str>) -> web::Data<AppState> {
+        let path = path.to_str().unwrap().to_string();
+        let sources = vec![DictionarySource {
+            name: "en".to_string(),
+            cache_path: format!("{}.bin", path),
+            path,
+        }];
+        let dictionaries = load_dictionaries(&sources).unwrap();
+        web::Data::new(AppState::new(
+            dictionaries,
+            sources,
+            "en".to_string(),
+            reload_token.map(str::to_string),
+        ))
+    }
+
+    fn solve_request() -> actix_test::TestRequest {
+        actix_test::TestRequest::post()
+            .uri("/solve")
+            .set_json(serde_json::json!({"letters": "aelp", "present": "a"}))
+    }
+
+    #[actix_web::test]
+    async fn test_reload_requires_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.txt");
+        std::fs::write(&path, "pale\nleap\n").unwrap();
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(file_state(&path, Some("secret")))
+                .service(reload),
+        )
+        .await;
+
+        for token in [None, Some("wrong")] {
+            let mut req = actix_test::TestRequest::post().uri("/reload");
+            if let Some(token) = token {
+                req = req.insert_header(("X-Reload-Token", token));
+            }
+            let resp = actix_test::call_service(&app, req.to_request()).await;
+            assert_eq!(resp.status(), 401, "token {:?}", token);
+            let body: serde_json::Value = actix_test::read_body_json(resp).await;
+            assert_eq!(body["error"]["code"], "unauthorized");
+        }
+
+        let req = actix_test::TestRequest::post()
+            .uri("/reload")
+            .insert_header(("X-Reload-Token", "secret"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+    }
+
+    #[actix_web::test]
+    async fn test_reload_swaps_in_changed_word_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.txt");
+        std::fs::write(&path, "pale\nleap\n").unwrap();
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(file_state(&path, None))
+                .service(solve_puzzle)
+                .service(reload),
+        )
+        .await;
+
+        let result: SolveResult =
+            actix_test::call_and_read_body_json(&app, solve_request().to_request()).await;
+        assert_eq!(result.count, 2);
+
+        std::fs::write(&path, "pale\nleap\nplea\napple\n").unwrap();
+        // Make the word list unambiguously newer than the compiled cache
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+
+        let req = actix_test::TestRequest::post().uri("/reload").to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["word_count"], 4);
+        assert_eq!(body["dictionaries"]["en"], 4);
+
+        let result: SolveResult =
+            actix_test::call_and_read_body_json(&app, solve_request().to_request()).await;
+        assert_eq!(result.words, vec!["apple", "leap", "pale", "plea"]);
+    }
+
+    #[actix_web::test]
+    async fn test_reload_failure_keeps_old_dictionary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.txt");
+        std::fs::write(&path, "pale\nleap\n").unwrap();
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(file_state(&path, None))
+                .service(solve_puzzle)
+                .service(reload),
+        )
+        .await;
+
+        std::fs::remove_file(&path).unwrap();
+        let req = actix_test::TestRequest::post().uri("/reload").to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 500);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["error"]["code"], "reload_failed");
+
+        let result: SolveResult =
+            actix_test::call_and_read_body_json(&app, solve_request().to_request()).await;
+        assert_eq!(
+            result.words,
+            vec!["leap", "pale"],
+            "old dictionary still live"
+        );
+    }
+
+    #[test]
+    fn test_parse_dictionary_sources() {
+        let sources = parse_dictionary_sources("en=data/en.txt, fr = data/fr.txt").unwrap();
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[1].name, "fr");
+        assert_eq!(sources[1].path, "data/fr.txt");
+        assert_eq!(sources[1].cache_path, "data/fr.txt.bin");
+
+        assert!(parse_dictionary_sources("en").is_err());
+        assert!(parse_dictionary_sources("en=a,en=b").is_err());
+        assert!(parse_dictionary_sources("").is_err());
+    }
 }
@@ -0,0 +1,95 @@
+//! Bloom filter letting [`Dictionary`](crate::Dictionary) reject absent words
+//! without walking the trie.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Bits allotted per expected item. With [`PROBES`] probes this keeps the
+/// false-positive rate near 1%.
+const BITS_PER_ITEM: usize = 10;
+
+/// Bit positions set (and checked) per item.
+const PROBES: u64 = 7;
+
+/// A fixed-size set of hashed strings that can answer "definitely absent".
+///
+/// Lookups never report a false negative; a positive must be confirmed by
+/// the caller.
+#[derive(Debug, Clone)]
+pub(crate) struct BloomFilter {
+    bits: Vec<u64>,
+    len: u64,
+}
+
+impl BloomFilter {
+    /// An empty filter sized for `items` entries.
+    pub(crate) fn with_capacity(items: usize) -> Self {
+        let len = (items.max(1) * BITS_PER_ITEM).next_multiple_of(64);
+        Self {
+            bits: vec![0; len / 64],
+            len: len as u64,
+        }
+    }
+
+    pub(crate) fn insert(&mut self, item: &str) {
+        for bit in positions(item, self.len) {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+
+    /// `false` only when `item` was never inserted.
+    pub(crate) fn might_contain(&self, item: &str) -> bool {
+        positions(item, self.len).all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+}
+
+/// The [`PROBES`] bit positions of `item`, derived from two hashes by
+/// double hashing.
+fn positions(item: &str, len: u64) -> impl Iterator<Item = u64> {
+    let mut hasher = DefaultHasher::new();
+    item.hash(&mut hasher);
+    let h1 = hasher.finish();
+    hasher.write_u8(0xff);
+    // Odd, so successive probes never collapse onto one bit
+    let h2 = hasher.finish() | 1;
+    (0..PROBES).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(prefix: &str, count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("{}{}", prefix, i)).collect()
+    }
+
+    #[test]
+    fn test_no_false_negatives() {
+        let known = words("word", 5000);
+        let mut filter = BloomFilter::with_capacity(known.len());
+        for word in &known {
+            filter.insert(word);
+        }
+        assert!(known.iter().all(|word| filter.might_contain(word)));
+    }
+
+    #[test]
+    fn test_false_positive_rate_is_low() {
+        let mut filter = BloomFilter::with_capacity(10_000);
+        for word in words("in", 10_000) {
+            filter.insert(&word);
+        }
+        let false_positives = words("out", 10_000)
+            .iter()
+            .filter(|word| filter.might_contain(word))
+            .count();
+        assert!(false_positives < 300, "{} false positives", false_positives);
+    }
+
+    #[test]
+    fn test_empty_filter_rejects_everything() {
+        let filter = BloomFilter::with_capacity(0);
+        assert!(!filter.might_contain("apple"));
+        assert!(!filter.might_contain(""));
+    }
+}
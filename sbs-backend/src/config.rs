@@ -1,31 +1,105 @@
 //! Configuration management.
 
+use crate::dictionary::{LoadOptions, DEFAULT_MAX_WORD_LENGTH};
 use crate::error::SbsError;
+use crate::solver::Solver;
 #[cfg(feature = "validator")]
 use crate::validator::ValidatorKind;
-use serde::{Deserialize, Serialize};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-const DEFAULT_MIN_LENGTH: usize = 4;
+/// Minimum word length when `minimal-word-length` is not set. Variants that
+/// allow shorter words (e.g. some non-English Bees use 3) override it per config.
+pub const DEFAULT_MIN_LENGTH: usize = 4;
+/// Minimum word length enforced by [`PuzzleMode::Nyt`].
+const NYT_MIN_LENGTH: usize = 4;
 const DEFAULT_DICT_PATH: &str = "data/dictionary.txt";
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Config {
     pub letters: Option<String>,
     pub present: Option<String>, // The obligatory letter(s)
+    /// Accept words containing at least this many distinct `present` letters
+    /// instead of all of them.
+    #[serde(rename = "required-min")]
+    pub required_min: Option<usize>,
     #[serde(rename = "minimal-word-length")]
     pub minimal_word_length: Option<usize>,
     #[serde(rename = "maximal-word-length")]
     pub maximal_word_length: Option<usize>,
+    /// Also collect words this long up to the minimal word length, reported
+    /// separately as bonus words.
+    #[serde(rename = "bonus-min-length")]
+    pub bonus_min_length: Option<usize>,
     pub output: Option<String>,
-    pub repeats: Option<usize>,
+    #[serde(default)]
+    pub repeats: Option<RepeatPolicy>,
     #[serde(rename = "case-sensitive")]
     pub case_sensitive: Option<bool>,
+    /// Regular expression every result must match. Unanchored: use `^...$`
+    /// to require the whole word to match. A template of letters and `_`
+    /// (e.g. `c__e`) matches whole words with `_` as a wildcard.
+    pub pattern: Option<String>,
+    /// Prefix every result must start with (matched case-insensitively).
+    #[serde(rename = "starts-with")]
+    pub starts_with: Option<String>,
+    /// Suffix every result must end with (matched case-insensitively).
+    #[serde(rename = "ends-with")]
+    pub ends_with: Option<String>,
+    /// Contiguous substring every result must contain (matched case-insensitively).
+    pub contains: Option<String>,
+    /// Words never returned, matched case-insensitively.
+    pub blocklist: Option<Vec<String>>,
+    /// When set, only these words are returned (if otherwise valid), matched
+    /// case-insensitively. The blocklist still wins.
+    #[serde(rename = "allowlist-only")]
+    pub allowlist_only: Option<Vec<String>>,
+    /// Letters no result may contain, even if they appear in `letters`.
+    #[serde(rename = "excluded-letters")]
+    pub excluded: Option<String>,
+    /// Upper bound on the number of results; longest words are kept first.
+    #[serde(rename = "max-results")]
+    pub max_results: Option<usize>,
+    /// Minimum number of distinct letters each result must use.
+    #[serde(rename = "min-distinct-letters")]
+    pub min_distinct_letters: Option<usize>,
+    /// Emit dictionary spellings (e.g. "Paris") instead of the lowercase match.
+    #[serde(rename = "preserve-case")]
+    pub preserve_case: Option<bool>,
+    /// Accept hyphenated and apostrophe words; `-` and `'` need not be in `letters`.
+    #[serde(rename = "allow-punctuation")]
+    pub allow_punctuation: Option<bool>,
+    /// Index accented dictionary words by their plain form ("café" as "cafe").
+    #[serde(rename = "fold-accents")]
+    pub fold_accents: Option<bool>,
+    /// Skip dictionary lines whose word is longer than this (default 64).
+    #[serde(rename = "max-word-length-on-load")]
+    pub max_word_length_on_load: Option<usize>,
+    /// Collapse inflected results (`-s`, `-es`, `-ed`, `-ing`) under their stem.
+    #[serde(rename = "group-inflections")]
+    pub group_inflections: Option<bool>,
+    /// Order of the result list; alphabetical when unset.
+    #[serde(rename = "sort-order")]
+    pub sort_order: Option<SortOrder>,
+    /// Puzzle rules; `nyt` enforces the NYT Spelling Bee constraints.
+    pub mode: Option<PuzzleMode>,
 
     // Path to the seed dictionary for generation
     #[serde(default = "default_dict_path")]
     pub dictionary: PathBuf,
+    /// Several word lists merged into one dictionary; takes precedence over
+    /// `dictionary` when set.
+    pub dictionaries: Option<Vec<PathBuf>>,
+    /// Download the word list from this URL instead of reading `dictionary`;
+    /// the download is cached locally.
+    #[cfg(feature = "validator")]
+    #[serde(rename = "dictionary-url")]
+    pub dictionary_url: Option<String>,
 
     // Validator selection
     #[cfg(feature = "validator")]
@@ -36,6 +110,171 @@ pub struct Config {
     #[cfg(feature = "validator")]
     #[serde(rename = "validator-url")]
     pub validator_url: Option<String>,
+    /// HTTP request timeout for online validators, in seconds (default 10).
+    #[cfg(feature = "validator")]
+    #[serde(rename = "validator-timeout")]
+    pub validator_timeout: Option<u64>,
+}
+
+/// How often a single letter may be reused within one word.
+///
+/// Deserializes from a number (`0` means [`RepeatPolicy::Once`], `n` means
+/// [`RepeatPolicy::Max`]) or from the strings `"once"` and `"unlimited"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepeatPolicy {
+    /// Letters may be reused any number of times (the Spelling Bee rule).
+    #[default]
+    Unlimited,
+    /// Each letter may appear at most `n` times.
+    Max(usize),
+    /// Each letter may appear at most once (isogram words).
+    Once,
+}
+
+impl RepeatPolicy {
+    /// Maximum occurrences of any single letter, or `None` when unlimited.
+    pub fn limit(&self) -> Option<usize> {
+        match self {
+            RepeatPolicy::Unlimited => None,
+            RepeatPolicy::Max(n) => Some((*n).max(1)),
+            RepeatPolicy::Once => Some(1),
+        }
+    }
+}
+
+/// Order in which results are listed. Ties always break alphabetically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortOrder {
+    /// Alphabetical.
+    #[default]
+    Alpha,
+    /// Shortest words first.
+    Length,
+    /// Longest words first.
+    LengthDesc,
+    /// Highest-scoring words first.
+    Score,
+    /// Most frequent words first, by the word list's frequency column.
+    Frequency,
+}
+
+impl std::str::FromStr for SortOrder {
+    type Err = SbsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "alpha" => Ok(SortOrder::Alpha),
+            "length" => Ok(SortOrder::Length),
+            "length-desc" => Ok(SortOrder::LengthDesc),
+            "score" => Ok(SortOrder::Score),
+            "frequency" => Ok(SortOrder::Frequency),
+            other => Err(SbsError::ConfigError(format!(
+                "Unknown sort order '{}'. Use alpha, length, length-desc, score, or frequency.",
+                other
+            ))),
+        }
+    }
+}
+
+/// Rule set the puzzle is solved under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum PuzzleMode {
+    /// Any number of required letters, with lengths and repeats as configured.
+    #[default]
+    Classic,
+    /// NYT Spelling Bee: exactly one center letter, words of at least four
+    /// letters, unlimited reuse, and pangrams highlighted.
+    Nyt,
+}
+
+impl std::str::FromStr for PuzzleMode {
+    type Err = SbsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "classic" => Ok(PuzzleMode::Classic),
+            "nyt" => Ok(PuzzleMode::Nyt),
+            other => Err(SbsError::ConfigError(format!(
+                "Unknown puzzle mode '{}'. Use classic or nyt.",
+                other
+            ))),
+        }
+    }
+}
+
+/// Mirrors the custom serde impls: a count or one of the named policies.
+impl JsonSchema for RepeatPolicy {
+    fn schema_name() -> String {
+        "RepeatPolicy".to_string()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        serde_json::from_value(serde_json::json!({
+            "description": "How often a single letter may be reused within one word: \
+                            a maximum count (0 means once) or \"once\" / \"unlimited\".",
+            "anyOf": [
+                { "type": "integer", "format": "uint", "minimum": 0 },
+                { "type": "string", "enum": ["once", "unlimited"] }
+            ]
+        }))
+        .expect("static schema is valid")
+    }
+}
+
+impl Serialize for RepeatPolicy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            RepeatPolicy::Unlimited => serializer.serialize_str("unlimited"),
+            RepeatPolicy::Max(n) => serializer.serialize_u64(*n as u64),
+            RepeatPolicy::Once => serializer.serialize_str("once"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RepeatPolicy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Count(usize),
+            Name(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Count(0) => Ok(RepeatPolicy::Once),
+            Raw::Count(n) => Ok(RepeatPolicy::Max(n)),
+            Raw::Name(name) => match name.as_str() {
+                "once" => Ok(RepeatPolicy::Once),
+                "unlimited" => Ok(RepeatPolicy::Unlimited),
+                other => Err(serde::de::Error::custom(format!(
+                    "invalid repeats '{}': expected a number, \"once\" or \"unlimited\"",
+                    other
+                ))),
+            },
+        }
+    }
+}
+
+fn parse_error(
+    format: &str,
+    message: impl std::fmt::Display,
+    location: Option<(usize, usize)>,
+) -> SbsError {
+    SbsError::ParseError {
+        message: format!("Invalid {} config: {}", format, message),
+        line: location.map(|(line, _)| line),
+        column: location.map(|(_, column)| column),
+    }
+}
+
+/// 1-based line and column of the byte `offset` in `content`.
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
 }
 
 fn default_dict_path() -> PathBuf {
@@ -47,26 +286,67 @@ impl Config {
         Self {
             letters: None,
             present: None,
+            required_min: None,
             minimal_word_length: Some(DEFAULT_MIN_LENGTH),
             maximal_word_length: None,
+            bonus_min_length: None,
             output: None,
             repeats: None,
             case_sensitive: None,
+            pattern: None,
+            starts_with: None,
+            ends_with: None,
+            contains: None,
+            blocklist: None,
+            allowlist_only: None,
+            excluded: None,
+            max_results: None,
+            min_distinct_letters: None,
+            preserve_case: None,
+            allow_punctuation: None,
+            fold_accents: None,
+            max_word_length_on_load: None,
+            group_inflections: None,
+            sort_order: None,
+            mode: None,
             dictionary: default_dict_path(),
+            dictionaries: None,
+            #[cfg(feature = "validator")]
+            dictionary_url: None,
             #[cfg(feature = "validator")]
             validator: None,
             #[cfg(feature = "validator")]
             api_key: None,
             #[cfg(feature = "validator")]
             validator_url: None,
+            #[cfg(feature = "validator")]
+            validator_timeout: None,
         }
     }
 
+    /// Load a config file, parsed by extension: `.toml`, `.yaml`/`.yml`, or
+    /// JSON for anything else.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, SbsError> {
+        let path = path.as_ref();
         let content = fs::read_to_string(path)?;
-        let config: Config = serde_json::from_str(&content)
-            .map_err(|e| SbsError::SerializationError(e.to_string()))?;
-        Ok(config)
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("toml") => toml::from_str(&content).map_err(|e| {
+                let location = e.span().map(|span| line_column(&content, span.start));
+                parse_error("TOML", e.message(), location)
+            }),
+            Some("yaml" | "yml") => serde_yaml::from_str(&content).map_err(|e| {
+                let location = e.location().map(|l| (l.line(), l.column()));
+                parse_error("YAML", e, location)
+            }),
+            _ => serde_json::from_str(&content).map_err(|e| {
+                let location = (e.line() > 0).then(|| (e.line(), e.column()));
+                parse_error("JSON", e, location)
+            }),
+        }
     }
 
     /// Fluent API: Set letters
@@ -80,6 +360,231 @@ impl Config {
         self.present = Some(present.to_string());
         self
     }
+
+    /// Fluent API: Set minimal word length
+    pub fn with_min_length(mut self, length: usize) -> Self {
+        self.minimal_word_length = Some(length);
+        self
+    }
+
+    /// Fluent API: Set maximal word length
+    pub fn with_max_length(mut self, length: usize) -> Self {
+        self.maximal_word_length = Some(length);
+        self
+    }
+
+    /// Fluent API: Set letter repeat policy
+    pub fn with_repeats(mut self, repeats: RepeatPolicy) -> Self {
+        self.repeats = Some(repeats);
+        self
+    }
+
+    /// Fluent API: Set case sensitivity
+    pub fn with_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = Some(case_sensitive);
+        self
+    }
+
+    /// Fluent API: Set dictionary path
+    pub fn with_dictionary<P: Into<PathBuf>>(mut self, dictionary: P) -> Self {
+        self.dictionary = dictionary.into();
+        self
+    }
+
+    /// Fluent API: Set output file path
+    pub fn with_output(mut self, output: &str) -> Self {
+        self.output = Some(output.to_string());
+        self
+    }
+
+    /// Layer `other` over this config: every `Some` field in `other` replaces
+    /// the one here, and every `None` leaves it untouched. `dictionary` is not
+    /// optional, so it only overrides when `other` names a path other than the
+    /// default; an explicit default path never shadows a configured one.
+    pub fn merge(&mut self, other: Config) {
+        self.letters = other.letters.or(self.letters.take());
+        self.present = other.present.or(self.present.take());
+        self.required_min = other.required_min.or(self.required_min);
+        self.minimal_word_length = other.minimal_word_length.or(self.minimal_word_length);
+        self.maximal_word_length = other.maximal_word_length.or(self.maximal_word_length);
+        self.bonus_min_length = other.bonus_min_length.or(self.bonus_min_length);
+        self.output = other.output.or(self.output.take());
+        self.repeats = other.repeats.or(self.repeats);
+        self.case_sensitive = other.case_sensitive.or(self.case_sensitive);
+        self.pattern = other.pattern.or(self.pattern.take());
+        self.starts_with = other.starts_with.or(self.starts_with.take());
+        self.ends_with = other.ends_with.or(self.ends_with.take());
+        self.contains = other.contains.or(self.contains.take());
+        self.blocklist = other.blocklist.or(self.blocklist.take());
+        self.allowlist_only = other.allowlist_only.or(self.allowlist_only.take());
+        self.excluded = other.excluded.or(self.excluded.take());
+        self.max_results = other.max_results.or(self.max_results);
+        self.min_distinct_letters = other.min_distinct_letters.or(self.min_distinct_letters);
+        self.preserve_case = other.preserve_case.or(self.preserve_case);
+        self.allow_punctuation = other.allow_punctuation.or(self.allow_punctuation);
+        self.fold_accents = other.fold_accents.or(self.fold_accents);
+        self.max_word_length_on_load = other
+            .max_word_length_on_load
+            .or(self.max_word_length_on_load);
+        self.group_inflections = other.group_inflections.or(self.group_inflections);
+        self.sort_order = other.sort_order.or(self.sort_order);
+        self.mode = other.mode.or(self.mode);
+        if other.dictionary != default_dict_path() {
+            self.dictionary = other.dictionary;
+        }
+        self.dictionaries = other.dictionaries.or(self.dictionaries.take());
+        #[cfg(feature = "validator")]
+        {
+            self.dictionary_url = other.dictionary_url.or(self.dictionary_url.take());
+            self.validator = other.validator.or(self.validator.take());
+            self.api_key = other.api_key.or(self.api_key.take());
+            self.validator_url = other.validator_url.or(self.validator_url.take());
+            self.validator_timeout = other.validator_timeout.or(self.validator_timeout);
+        }
+    }
+
+    /// Owned form of [`Config::merge`].
+    pub fn merged(mut self, other: Config) -> Self {
+        self.merge(other);
+        self
+    }
+
+    /// Dictionary loader options implied by this config.
+    pub fn load_options(&self) -> LoadOptions {
+        LoadOptions {
+            allow_punctuation: self.allow_punctuation.unwrap_or(false),
+            fold_accents: self.fold_accents.unwrap_or(false),
+            max_word_length: self
+                .max_word_length_on_load
+                .unwrap_or(DEFAULT_MAX_WORD_LENGTH),
+        }
+    }
+
+    /// Reject configs that cannot describe a solvable puzzle.
+    pub fn validate(&self) -> Result<(), SbsError> {
+        let letters = self.letters.as_deref().unwrap_or("").trim();
+        if letters.is_empty() {
+            return Err(SbsError::ConfigError(
+                "letters must not be empty".to_string(),
+            ));
+        }
+
+        if self.present.as_deref().is_some_and(|p| p.trim().is_empty()) {
+            return Err(SbsError::ConfigError(
+                "present must not be empty".to_string(),
+            ));
+        }
+
+        if self.dictionaries.as_ref().is_some_and(Vec::is_empty) {
+            return Err(SbsError::ConfigError(
+                "dictionaries must list at least one file".to_string(),
+            ));
+        }
+
+        let available = letters.to_lowercase();
+        for c in self.present.as_deref().unwrap_or("").chars() {
+            if !available.contains(c.to_ascii_lowercase()) {
+                return Err(SbsError::ConfigError(format!(
+                    "required letter '{}' is not among available letters",
+                    c
+                )));
+            }
+        }
+
+        if let Some(required_min) = self.required_min {
+            let distinct: HashSet<char> = self
+                .present
+                .as_deref()
+                .unwrap_or("")
+                .to_lowercase()
+                .chars()
+                .collect();
+            if required_min > distinct.len() {
+                return Err(SbsError::ConfigError(format!(
+                    "required-min {} exceeds the {} required letters",
+                    required_min,
+                    distinct.len()
+                )));
+            }
+        }
+
+        if let (Some(min), Some(max)) = (self.minimal_word_length, self.maximal_word_length) {
+            if min > max {
+                return Err(SbsError::ConfigError(format!(
+                    "minimal word length {} exceeds maximal word length {}",
+                    min, max
+                )));
+            }
+        }
+
+        if self.repeats == Some(RepeatPolicy::Max(0)) {
+            return Err(SbsError::ConfigError(
+                "repeats must allow each letter at least once".to_string(),
+            ));
+        }
+
+        if let Some(pattern) = &self.pattern {
+            Solver::compile_pattern(pattern)?;
+        }
+
+        self.validate_mode()
+    }
+
+    /// Reject configs that break the rules of the selected [`PuzzleMode`].
+    pub fn validate_mode(&self) -> Result<(), SbsError> {
+        if self.mode() != PuzzleMode::Nyt {
+            return Ok(());
+        }
+
+        let center = self.present.as_deref().unwrap_or("").trim();
+        if center.chars().count() != 1 {
+            return Err(SbsError::ConfigError(format!(
+                "nyt mode requires exactly one center letter, got '{}'",
+                center
+            )));
+        }
+        if let Some(min) = self.minimal_word_length {
+            if min < NYT_MIN_LENGTH {
+                return Err(SbsError::ConfigError(format!(
+                    "nyt mode requires a minimal word length of at least {}, got {}",
+                    NYT_MIN_LENGTH, min
+                )));
+            }
+        }
+        if self.repeat_policy() != RepeatPolicy::Unlimited {
+            return Err(SbsError::ConfigError(
+                "nyt mode requires unlimited letter repeats".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Effective repeat policy; unset means unlimited repetition.
+    pub fn repeat_policy(&self) -> RepeatPolicy {
+        self.repeats.unwrap_or_default()
+    }
+
+    /// JSON Schema for config files and request bodies, using the serde
+    /// (kebab-case) field names.
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(Config)).unwrap_or_default()
+    }
+
+    /// Effective minimal word length; unset means [`DEFAULT_MIN_LENGTH`].
+    pub fn min_length(&self) -> usize {
+        self.minimal_word_length.unwrap_or(DEFAULT_MIN_LENGTH)
+    }
+
+    /// Effective puzzle mode; unset means classic.
+    pub fn mode(&self) -> PuzzleMode {
+        self.mode.unwrap_or_default()
+    }
+
+    /// Effective result order; unset means alphabetical.
+    pub fn sort_order(&self) -> SortOrder {
+        self.sort_order.unwrap_or_default()
+    }
 }
 
 impl Default for Config {
@@ -87,3 +592,425 @@ impl Default for Config {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeat_policy_deserialization() {
+        let parse = |json: &str| serde_json::from_str::<Config>(json).unwrap().repeats;
+
+        assert_eq!(parse(r#"{"repeats":0}"#), Some(RepeatPolicy::Once));
+        assert_eq!(parse(r#"{"repeats":2}"#), Some(RepeatPolicy::Max(2)));
+        assert_eq!(parse(r#"{"repeats":"once"}"#), Some(RepeatPolicy::Once));
+        assert_eq!(
+            parse(r#"{"repeats":"unlimited"}"#),
+            Some(RepeatPolicy::Unlimited)
+        );
+        assert_eq!(parse(r#"{"repeats":null}"#), None);
+        assert_eq!(parse(r#"{}"#), None);
+        assert!(serde_json::from_str::<Config>(r#"{"repeats":"twice"}"#).is_err());
+    }
+
+    #[test]
+    fn test_repeat_policy_limit() {
+        assert_eq!(RepeatPolicy::Unlimited.limit(), None);
+        assert_eq!(RepeatPolicy::Max(3).limit(), Some(3));
+        assert_eq!(RepeatPolicy::Once.limit(), Some(1));
+        assert_eq!(Config::new().repeat_policy(), RepeatPolicy::Unlimited);
+    }
+
+    #[test]
+    fn test_sort_order_parsing() {
+        let parse = |json: &str| serde_json::from_str::<Config>(json).unwrap().sort_order();
+
+        assert_eq!(parse(r#"{}"#), SortOrder::Alpha);
+        assert_eq!(
+            parse(r#"{"sort-order":"length-desc"}"#),
+            SortOrder::LengthDesc
+        );
+        assert_eq!("score".parse::<SortOrder>().unwrap(), SortOrder::Score);
+        assert_eq!("length".parse::<SortOrder>().unwrap(), SortOrder::Length);
+        assert_eq!(
+            "frequency".parse::<SortOrder>().unwrap(),
+            SortOrder::Frequency
+        );
+        assert!("random".parse::<SortOrder>().is_err());
+    }
+
+    #[test]
+    fn test_merge_overrides_and_passes_through() {
+        let mut base = Config::new()
+            .with_letters("abcdefg")
+            .with_present("a")
+            .with_dictionary("words.txt");
+        base.pattern = Some("^a".to_string());
+        base.mode = Some(PuzzleMode::Nyt);
+
+        let mut overrides = Config::new().with_present("b");
+        overrides.minimal_word_length = None;
+        overrides.maximal_word_length = Some(8);
+        overrides.fold_accents = Some(true);
+        base.merge(overrides);
+
+        assert_eq!(base.present.as_deref(), Some("b"));
+        assert_eq!(base.maximal_word_length, Some(8));
+        assert_eq!(base.fold_accents, Some(true));
+        assert_eq!(base.letters.as_deref(), Some("abcdefg"));
+        assert_eq!(base.pattern.as_deref(), Some("^a"));
+        assert_eq!(base.mode, Some(PuzzleMode::Nyt));
+        assert_eq!(base.minimal_word_length, Some(DEFAULT_MIN_LENGTH));
+    }
+
+    #[test]
+    fn test_merge_dictionary_sentinel() {
+        let base = Config::new().with_dictionary("words.txt");
+        let kept = base.clone().merged(Config::new());
+        assert_eq!(kept.dictionary, PathBuf::from("words.txt"));
+
+        let explicit_default = base
+            .clone()
+            .merged(Config::new().with_dictionary(DEFAULT_DICT_PATH));
+        assert_eq!(explicit_default.dictionary, PathBuf::from("words.txt"));
+
+        let replaced = base.merged(Config::new().with_dictionary("other.txt"));
+        assert_eq!(replaced.dictionary, PathBuf::from("other.txt"));
+    }
+
+    #[test]
+    fn test_dictionaries_parse_and_load() {
+        use crate::Dictionary;
+        use std::io::Write;
+
+        let mut base = tempfile::NamedTempFile::new().unwrap();
+        writeln!(base, "fade\nbead").unwrap();
+        let mut names = tempfile::NamedTempFile::new().unwrap();
+        writeln!(names, "Abbe").unwrap();
+        let json = serde_json::json!({
+            "dictionary": "unused.txt",
+            "dictionaries": [base.path(), names.path()],
+        });
+
+        let config = load_as("json", &json.to_string()).unwrap();
+        let paths = config.dictionaries.clone().unwrap();
+        assert_eq!(paths, vec![base.path(), names.path()]);
+
+        let dict = Dictionary::from_files_with_options(&paths, config.load_options()).unwrap();
+        assert!(dict.contains("bead"), "from the first file");
+        assert!(dict.contains("abbe"), "from the second file");
+
+        let empty = Config {
+            dictionaries: Some(Vec::new()),
+            ..Config::new().with_letters("abcdefg").with_present("a")
+        };
+        assert!(empty.validate().is_err());
+    }
+
+    /// Write `content` to a temp file with the given extension and load it.
+    fn load_as(extension: &str, content: &str) -> Result<Config, SbsError> {
+        let file = tempfile::Builder::new()
+            .suffix(&format!(".{}", extension))
+            .tempfile()
+            .unwrap();
+        fs::write(file.path(), content).unwrap();
+        Config::from_file(file.path())
+    }
+
+    fn sample_config() -> Config {
+        let mut config = Config::new()
+            .with_letters("abcdefg")
+            .with_present("a")
+            .with_max_length(8)
+            .with_repeats(RepeatPolicy::Max(2))
+            .with_case_sensitive(true)
+            .with_dictionary("words.txt");
+        config.sort_order = Some(SortOrder::LengthDesc);
+        config
+    }
+
+    fn assert_same(loaded: &Config, expected: &Config) {
+        assert_eq!(loaded.letters, expected.letters);
+        assert_eq!(loaded.present, expected.present);
+        assert_eq!(loaded.minimal_word_length, expected.minimal_word_length);
+        assert_eq!(loaded.maximal_word_length, expected.maximal_word_length);
+        assert_eq!(loaded.repeats, expected.repeats);
+        assert_eq!(loaded.case_sensitive, expected.case_sensitive);
+        assert_eq!(loaded.sort_order, expected.sort_order);
+        assert_eq!(loaded.dictionary, expected.dictionary);
+    }
+
+    #[test]
+    fn test_from_file_toml_round_trip() {
+        let config = sample_config();
+        let loaded = load_as("toml", &toml::to_string(&config).unwrap()).unwrap();
+        assert_same(&loaded, &config);
+    }
+
+    #[test]
+    fn test_from_file_yaml_round_trip() {
+        let config = sample_config();
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        assert_same(&load_as("yaml", &yaml).unwrap(), &config);
+        assert_same(&load_as("yml", &yaml).unwrap(), &config);
+    }
+
+    #[test]
+    fn test_from_file_toml_kebab_case_keys() {
+        let loaded = load_as(
+            "toml",
+            "letters = \"abc\"\npresent = \"a\"\nminimal-word-length = 5\nrepeats = \"once\"\n",
+        )
+        .unwrap();
+        assert_eq!(loaded.minimal_word_length, Some(5));
+        assert_eq!(loaded.repeats, Some(RepeatPolicy::Once));
+    }
+
+    #[test]
+    fn test_from_file_unknown_extension_is_json() {
+        let loaded = load_as("conf", r#"{"letters":"abc"}"#).unwrap();
+        assert_eq!(loaded.letters.as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn test_from_file_error_names_format() {
+        let err = load_as("toml", "letters = ").unwrap_err();
+        assert!(err.to_string().contains("TOML"), "{}", err);
+        let err = load_as("yaml", "letters: [").unwrap_err();
+        assert!(err.to_string().contains("YAML"), "{}", err);
+        let err = load_as("json", "{").unwrap_err();
+        assert!(err.to_string().contains("JSON"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_config() {
+        let mut config = Config::new().with_letters("Ware").with_present("W");
+        config.maximal_word_length = Some(4);
+        assert!(config.validate().is_ok());
+        assert!(
+            Config::new().with_letters("abc").validate().is_ok(),
+            "present is optional"
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_letters() {
+        assert!(Config::new().validate().is_err());
+        let err = Config::new().with_letters(" ").validate().unwrap_err();
+        assert!(err.to_string().contains("letters must not be empty"));
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_present() {
+        let err = Config::new()
+            .with_letters("abc")
+            .with_present(" ")
+            .validate()
+            .unwrap_err();
+        assert!(err.to_string().contains("present must not be empty"));
+    }
+
+    #[test]
+    fn test_validate_rejects_present_outside_letters() {
+        let err = Config::new()
+            .with_letters("abc")
+            .with_present("az")
+            .validate()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("required letter 'z' is not among available letters"));
+    }
+
+    #[test]
+    fn test_validate_rejects_min_above_max() {
+        let mut config = Config::new().with_letters("abc");
+        config.minimal_word_length = Some(6);
+        config.maximal_word_length = Some(5);
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("minimal word length 6 exceeds"));
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_repeats() {
+        let mut config = Config::new().with_letters("abc");
+        config.repeats = Some(RepeatPolicy::Max(0));
+        assert!(config.validate().is_err());
+        config.repeats = Some(RepeatPolicy::Once);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_pattern() {
+        let mut config = Config::new().with_letters("abc");
+        config.pattern = Some("(ab".to_string());
+        let err = config.validate().unwrap_err();
+        assert!(matches!(err, SbsError::ConfigError(_)));
+        assert!(err.to_string().contains("Invalid pattern '(ab'"));
+
+        // Not a pure letters-and-underscores template, so compiled as a regex
+        config.pattern = Some("c_[e".to_string());
+        assert!(matches!(config.validate(), Err(SbsError::ConfigError(_))));
+
+        config.pattern = Some("c__e".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_builder_sets_all_fields() {
+        let config = Config::new()
+            .with_letters("abcdefg")
+            .with_present("a")
+            .with_min_length(5)
+            .with_max_length(9)
+            .with_repeats(RepeatPolicy::Once)
+            .with_case_sensitive(true)
+            .with_dictionary("words.txt")
+            .with_output("out.txt");
+
+        assert_eq!(config.letters.as_deref(), Some("abcdefg"));
+        assert_eq!(config.present.as_deref(), Some("a"));
+        assert_eq!(config.minimal_word_length, Some(5));
+        assert_eq!(config.maximal_word_length, Some(9));
+        assert_eq!(config.repeats, Some(RepeatPolicy::Once));
+        assert_eq!(config.case_sensitive, Some(true));
+        assert_eq!(config.dictionary, PathBuf::from("words.txt"));
+        assert_eq!(config.output.as_deref(), Some("out.txt"));
+    }
+
+    fn nyt_config() -> Config {
+        let mut config = Config::new().with_letters("abcdefg").with_present("a");
+        config.mode = Some(PuzzleMode::Nyt);
+        config
+    }
+
+    #[test]
+    fn test_puzzle_mode_parsing() {
+        assert_eq!(Config::new().mode(), PuzzleMode::Classic);
+        assert_eq!("nyt".parse::<PuzzleMode>().unwrap(), PuzzleMode::Nyt);
+        assert_eq!(
+            "classic".parse::<PuzzleMode>().unwrap(),
+            PuzzleMode::Classic
+        );
+        assert!("bee".parse::<PuzzleMode>().is_err());
+        let config: Config = serde_json::from_str(r#"{"mode":"nyt"}"#).unwrap();
+        assert_eq!(config.mode(), PuzzleMode::Nyt);
+    }
+
+    #[test]
+    fn test_validate_nyt_accepts_single_center_letter() {
+        assert!(nyt_config().validate().is_ok());
+        let mut config = nyt_config().with_min_length(5);
+        config.repeats = Some(RepeatPolicy::Unlimited);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_nyt_rejects_multiple_center_letters() {
+        let err = nyt_config().with_present("ab").validate().unwrap_err();
+        assert!(
+            err.to_string().contains("exactly one center letter"),
+            "{}",
+            err
+        );
+        assert!(nyt_config().with_present("").validate().is_err());
+
+        let mut classic = nyt_config().with_present("ab");
+        classic.mode = Some(PuzzleMode::Classic);
+        assert!(classic.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_nyt_rejects_short_words() {
+        let err = nyt_config().with_min_length(3).validate().unwrap_err();
+        assert!(err.to_string().contains("at least 4, got 3"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_nyt_rejects_limited_repeats() {
+        let err = nyt_config()
+            .with_repeats(RepeatPolicy::Once)
+            .validate()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("unlimited letter repeats"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_json_schema_uses_serde_names() {
+        let schema = Config::json_schema();
+        let properties = schema["properties"].as_object().unwrap();
+
+        for name in [
+            "letters",
+            "present",
+            "minimal-word-length",
+            "maximal-word-length",
+            "case-sensitive",
+            "excluded-letters",
+            "sort-order",
+            "starts-with",
+            "dictionary",
+        ] {
+            assert!(properties.contains_key(name), "missing {}", name);
+        }
+        assert!(!properties.contains_key("minimal_word_length"));
+        assert!(!properties.contains_key("excluded"));
+
+        let repeats = serde_json::to_string(&schema).unwrap();
+        assert!(repeats.contains(r#""enum":["once","unlimited"]"#));
+        assert!(repeats.contains(r#""length-desc""#));
+    }
+
+    fn parse_location(extension: &str, content: &str) -> (Option<usize>, Option<usize>) {
+        match load_as(extension, content).unwrap_err() {
+            SbsError::ParseError { line, column, .. } => (line, column),
+            other => panic!("expected ParseError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_file_parse_error_location() {
+        let json = "{\n  \"letters\": \"abc\",\n  \"present\": a\n}";
+        assert_eq!(parse_location("json", json), (Some(3), Some(14)));
+
+        let toml = "letters = \"abc\"\npresent = =\n";
+        assert_eq!(parse_location("toml", toml).0, Some(2));
+
+        let yaml = "letters: abc\nminimal-word-length: [\n";
+        assert!(parse_location("yaml", yaml).0.is_some());
+    }
+
+    #[test]
+    fn test_parse_error_display_is_readable() {
+        let err = load_as("json", "{\"letters\": }").unwrap_err();
+        let text = err.to_string();
+        assert!(
+            text.starts_with("Parse error: Invalid JSON config:"),
+            "{}",
+            text
+        );
+        assert!(text.contains("line 1 column 13"), "{}", text);
+    }
+
+    #[test]
+    fn test_line_column() {
+        assert_eq!(line_column("abc", 0), (1, 1));
+        assert_eq!(line_column("ab\ncd", 4), (2, 2));
+        assert_eq!(line_column("ab", 99), (1, 3));
+    }
+
+    #[test]
+    fn test_validate_required_min() {
+        let mut config = Config::new().with_letters("abcdefz").with_present("afz");
+        config.required_min = Some(3);
+        assert!(config.validate().is_ok());
+
+        config.required_min = Some(4);
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("required-min 4 exceeds the 3 required letters"));
+    }
+}
@@ -1,62 +1,581 @@
 //! Dictionary data structure and loading logic.
 
+#[cfg(feature = "bloom")]
+use crate::bloom::BloomFilter;
 use crate::error::SbsError;
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, BufWriter, Read};
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Leading bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Leading bytes of a compiled dictionary file.
+const COMPILED_MAGIC: [u8; 4] = *b"SBSD";
+
+/// Layout version of the compiled format. Bump whenever `TrieNode` changes.
+const COMPILED_VERSION: u32 = 3;
+
+/// Time allowed for downloading a word list in [`Dictionary::from_url_with_options`].
+#[cfg(feature = "validator")]
+const DOWNLOAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Source of [`Dictionary::revision`] values; never reused within a process.
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(0);
+
+fn next_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
+}
 
 /// Represents a node in the Trie.
 /// Public so Solver can traverse it.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct TrieNode {
     pub children: HashMap<char, TrieNode>,
     pub is_end_of_word: bool,
+    /// Source spelling of the word ending here, kept only when it differs from
+    /// the indexed form (e.g. proper nouns, or accented words under
+    /// [`LoadOptions::fold_accents`]). An exact entry for the same word wins.
+    #[serde(default)]
+    pub original: Option<String>,
+    /// Frequency weight of the word ending here, from an optional
+    /// `word<TAB>count` column; 0 when the word list gives none.
+    #[serde(default)]
+    pub weight: u64,
 }
 
 impl TrieNode {
     fn insert(&mut self, word: &str) {
+        self.insert_with_original(word, word);
+    }
+
+    /// Insert `word` and return its terminal node.
+    fn insert_with_original(&mut self, word: &str, original: &str) -> &mut TrieNode {
         let mut node = self;
         for ch in word.chars() {
             node = node.children.entry(ch).or_default();
         }
+        if !node.is_end_of_word {
+            node.original = (original != word).then(|| original.to_string());
+        } else if original == word {
+            node.original = None;
+        }
         node.is_end_of_word = true;
+        node
+    }
+
+    /// Unmark the word spelled by `word`, pruning nodes left with no word at or
+    /// below them. Returns whether the word was present.
+    fn remove(&mut self, word: &[char]) -> bool {
+        let Some((first, rest)) = word.split_first() else {
+            let present = self.is_end_of_word;
+            self.is_end_of_word = false;
+            self.original = None;
+            self.weight = 0;
+            return present;
+        };
+        let Some(child) = self.children.get_mut(first) else {
+            return false;
+        };
+        let removed = child.remove(rest);
+        if removed && !child.is_end_of_word && child.children.is_empty() {
+            self.children.remove(first);
+        }
+        removed
+    }
+}
+
+/// Shape and size of a loaded dictionary, see [`Dictionary::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DictionaryStats {
+    pub word_count: usize,
+    /// Number of trie nodes, including the root.
+    pub node_count: usize,
+    /// Depth of the deepest node; the root has depth 0.
+    pub max_depth: usize,
+    pub longest_word_len: usize,
+}
+
+/// Punctuation permitted inside words when [`LoadOptions::allow_punctuation`] is set.
+pub const CONNECTORS: [char; 2] = ['-', '\''];
+
+/// Longest word (in characters) loaded from a word list by default.
+pub const DEFAULT_MAX_WORD_LENGTH: usize = 64;
+
+/// Options controlling which dictionary lines are accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// Keep words with inner hyphens or apostrophes, e.g. "well-being", "o'clock".
+    pub allow_punctuation: bool,
+    /// Index words by their unaccented form: "Café" is lowercased to "café",
+    /// then NFKD-normalized with combining marks stripped to "cafe". The
+    /// source spelling stays available as the node's `original`.
+    pub fold_accents: bool,
+    /// Lines whose word is longer than this many characters are skipped, and
+    /// overlong lines are never buffered whole, so a malformed file cannot
+    /// bloat memory.
+    pub max_word_length: usize,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            allow_punctuation: false,
+            fold_accents: false,
+            max_word_length: DEFAULT_MAX_WORD_LENGTH,
+        }
     }
 }
 
-/// A read-only container for the word list.
+/// A container for the word list, stored as a trie.
 pub struct Dictionary {
     pub root: TrieNode,
+    options: LoadOptions,
+    /// Built after each bulk load; `None` means lookups go straight to the trie.
+    #[cfg(feature = "bloom")]
+    bloom: Option<BloomFilter>,
+    revision: u64,
 }
 
 impl Dictionary {
     pub fn new() -> Self {
+        Self::with_options(LoadOptions::default())
+    }
+
+    /// An empty dictionary whose loaders and `insert_word` apply `options`.
+    pub fn with_options(options: LoadOptions) -> Self {
         Self {
             root: TrieNode::default(),
+            options,
+            #[cfg(feature = "bloom")]
+            bloom: None,
+            revision: next_revision(),
         }
     }
 
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, SbsError> {
-        let path_ref = path.as_ref();
-        if !path_ref.exists() {
+        Self::from_file_with_options(path, LoadOptions::default())
+    }
+
+    pub fn from_file_with_options<P: AsRef<Path>>(
+        path: P,
+        options: LoadOptions,
+    ) -> Result<Self, SbsError> {
+        Self::from_reader_with_options(Self::open(path.as_ref())?, options)
+    }
+
+    /// Download a word list from `url` and load it, keeping a copy in
+    /// `cache_dir` so later calls with the same URL skip the download.
+    ///
+    /// The cached file is named by a hash of the URL. Gzip responses are
+    /// decompressed like gzip files.
+    #[cfg(feature = "validator")]
+    pub fn from_url_with_options<P: AsRef<Path>>(
+        url: &str,
+        cache_dir: P,
+        options: LoadOptions,
+    ) -> Result<Self, SbsError> {
+        let cache_dir = cache_dir.as_ref();
+        let cached = cache_dir.join(format!("sbs-dict-{:016x}.txt", url_hash(url)));
+        if !cached.exists() {
+            Self::download(url, &cached)?;
+        }
+        Self::from_file_with_options(cached, options)
+    }
+
+    /// Stream the body at `url` into `path`, via a temporary file so an
+    /// interrupted download never leaves a truncated cache entry.
+    #[cfg(feature = "validator")]
+    fn download(url: &str, path: &Path) -> Result<(), SbsError> {
+        let client = crate::validator::http_client(DOWNLOAD_TIMEOUT)?;
+        let download_error = |e: &dyn std::fmt::Display| {
+            SbsError::DictionaryError(format!("Failed to download dictionary from {}: {}", url, e))
+        };
+        let mut response = client.get(url).send().map_err(|e| download_error(&e))?;
+        if !response.status().is_success() {
+            return Err(download_error(&format!(
+                "server returned status {}",
+                response.status()
+            )));
+        }
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let partial = path.with_extension("part");
+        let mut file = File::create(&partial)?;
+        response
+            .copy_to(&mut file)
+            .map_err(|e| download_error(&e))?;
+        std::fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    /// Load several word lists into one dictionary; duplicates collapse in the trie.
+    pub fn from_files<P: AsRef<Path>>(paths: &[P]) -> Result<Self, SbsError> {
+        Self::from_files_with_options(paths, LoadOptions::default())
+    }
+
+    pub fn from_files_with_options<P: AsRef<Path>>(
+        paths: &[P],
+        options: LoadOptions,
+    ) -> Result<Self, SbsError> {
+        let mut dictionary = Self::with_options(options);
+        for path in paths {
+            let path_ref = path.as_ref();
+            Self::open(path_ref)
+                .and_then(|reader| dictionary.load_lines(reader))
+                .map_err(|e| {
+                    SbsError::DictionaryError(format!("Failed to load {:?}: {}", path_ref, e))
+                })?;
+        }
+        dictionary.build_bloom();
+        Ok(dictionary)
+    }
+
+    /// Build a dictionary from any line-oriented source, one word per line.
+    ///
+    /// Words are trimmed and lowercased, keeping the source spelling of
+    /// capitalized entries; blank lines and words containing non-alphabetic
+    /// characters are skipped.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, SbsError> {
+        Self::from_reader_with_options(reader, LoadOptions::default())
+    }
+
+    pub fn from_reader_with_options<R: BufRead>(
+        reader: R,
+        options: LoadOptions,
+    ) -> Result<Self, SbsError> {
+        let mut dictionary = Self::with_options(options);
+        dictionary.load_lines(reader)?;
+        dictionary.build_bloom();
+        Ok(dictionary)
+    }
+
+    /// Add a word, normalized the same way as words read from a file.
+    ///
+    /// Words that the loader would skip are ignored.
+    pub fn insert_word(&mut self, word: &str) {
+        self.insert_weighted(word, 0);
+    }
+
+    /// Like [`Dictionary::insert_word`], recording a frequency `weight`.
+    /// A word listed more than once keeps its highest weight.
+    pub fn insert_weighted(&mut self, word: &str, weight: u64) {
+        let original = word.trim();
+        if let Some(clean_word) = self.clean(original) {
+            let node = self.root.insert_with_original(&clean_word, original);
+            node.weight = node.weight.max(weight);
+            self.revision = next_revision();
+            #[cfg(feature = "bloom")]
+            if let Some(bloom) = &mut self.bloom {
+                bloom.insert(&clean_word);
+            }
+        }
+    }
+
+    /// Rebuild the bloom filter from every word in the trie; a no-op
+    /// without the `bloom` feature.
+    fn build_bloom(&mut self) {
+        #[cfg(feature = "bloom")]
+        {
+            fn walk(node: &TrieNode, word: &mut String, bloom: &mut BloomFilter) {
+                if node.is_end_of_word {
+                    bloom.insert(word);
+                }
+                for (ch, child) in &node.children {
+                    word.push(*ch);
+                    walk(child, word, bloom);
+                    word.pop();
+                }
+            }
+
+            let mut bloom = BloomFilter::with_capacity(self.word_count());
+            walk(&self.root, &mut String::new(), &mut bloom);
+            self.bloom = Some(bloom);
+        }
+    }
+
+    /// Remove a word, normalized the same way as words read from a file.
+    ///
+    /// Returns whether the word was present. Longer words sharing its prefix
+    /// are kept; branches left empty are pruned to reclaim memory. The bloom
+    /// filter cannot forget words, so it keeps answering "maybe" for them and
+    /// the trie has the final say.
+    pub fn remove_word(&mut self, word: &str) -> bool {
+        match self.clean(word.trim()) {
+            Some(clean_word) => {
+                let chars: Vec<char> = clean_word.chars().collect();
+                let removed = self.root.remove(&chars);
+                if removed {
+                    self.revision = next_revision();
+                }
+                removed
+            }
+            None => false,
+        }
+    }
+
+    /// Open a word list, transparently decompressing gzip files.
+    fn open(path: &Path) -> Result<Box<dyn BufRead>, SbsError> {
+        if !path.exists() {
             return Err(SbsError::DictionaryError(format!(
                 "Dictionary file not found at {:?}.",
-                path_ref
+                path
             )));
         }
 
-        let file = File::open(path_ref)?;
-        let reader = BufReader::new(file);
-        let mut root = TrieNode::default();
+        let file = File::open(path)?;
+        let mut buffered = BufReader::new(file);
+        // Detect gzip by magic bytes so `.gz` files and renamed archives both work
+        let is_gzip = buffered.fill_buf()?.starts_with(&GZIP_MAGIC);
+        if is_gzip {
+            Ok(Box::new(BufReader::new(GzDecoder::new(buffered))))
+        } else {
+            Ok(Box::new(buffered))
+        }
+    }
+
+    fn load_lines<R: BufRead>(&mut self, mut reader: R) -> Result<(), SbsError> {
+        let max = self.options.max_word_length;
+        // Room for a word of multi-byte chars plus a frequency column and line ending
+        let byte_budget = max.saturating_mul(4).saturating_add(32);
+        let mut skipped = 0;
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            let read = (&mut reader)
+                .take(byte_budget as u64 + 1)
+                .read_until(b'\n', &mut buf)?;
+            if read == 0 {
+                break;
+            }
+            if buf.len() > byte_budget && !buf.ends_with(b"\n") {
+                Self::skip_line(&mut reader)?;
+                skipped += 1;
+                continue;
+            }
+
+            let line = std::str::from_utf8(&buf)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+                .trim_end_matches(['\n', '\r']);
+            // An optional trailing `<TAB>count` column carries the word's frequency
+            let (word, weight) = match line.rsplit_once('\t') {
+                Some((word, count)) => match count.trim().parse() {
+                    Ok(weight) => (word, weight),
+                    Err(_) => (line, 0),
+                },
+                None => (line, 0),
+            };
+            if word.trim().chars().count() > max {
+                skipped += 1;
+                continue;
+            }
+            self.insert_weighted(word, weight);
+        }
+        if skipped > 0 {
+            log::warn!(
+                "Skipped {} dictionary lines longer than {} characters",
+                skipped,
+                max
+            );
+        }
+        Ok(())
+    }
 
-        for line in reader.lines() {
-            let word = line?;
-            let clean_word = word.trim().to_lowercase();
-            if !clean_word.is_empty() && clean_word.chars().all(char::is_alphabetic) {
-                root.insert(&clean_word);
+    /// Discard input up to and including the next newline.
+    fn skip_line<R: BufRead>(reader: &mut R) -> Result<(), SbsError> {
+        loop {
+            let available = reader.fill_buf()?;
+            if available.is_empty() {
+                return Ok(());
+            }
+            match available.iter().position(|&b| b == b'\n') {
+                Some(i) => {
+                    reader.consume(i + 1);
+                    return Ok(());
+                }
+                None => {
+                    let len = available.len();
+                    reader.consume(len);
+                }
             }
         }
-        Ok(Self { root })
+    }
+
+    fn clean(&self, word: &str) -> Option<String> {
+        let mut clean_word = word.trim().to_lowercase();
+        if self.options.fold_accents {
+            clean_word = clean_word
+                .nfkd()
+                .filter(|ch| !is_combining_mark(*ch))
+                .collect();
+        }
+        let valid = if self.options.allow_punctuation {
+            // Connectors may join letters but never start or end a word
+            clean_word.starts_with(char::is_alphabetic)
+                && clean_word.ends_with(char::is_alphabetic)
+                && clean_word
+                    .chars()
+                    .all(|ch| ch.is_alphabetic() || CONNECTORS.contains(&ch))
+        } else {
+            !clean_word.is_empty() && clean_word.chars().all(char::is_alphabetic)
+        };
+        valid.then_some(clean_word)
+    }
+
+    /// Identifies the dictionary's current contents: distinct for every
+    /// dictionary built in this process and changed by `insert_word` and
+    /// `remove_word`, so result caches can tell when their source changed.
+    /// Edits made directly through `root` are not tracked.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Whether `word`, normalized as on load, is a complete word in the trie.
+    pub fn contains(&self, word: &str) -> bool {
+        self.terminal(word).is_some()
+    }
+
+    /// Frequency weight of `word`; 0 when it is absent or has no weight.
+    pub fn weight(&self, word: &str) -> u64 {
+        self.terminal(word).map_or(0, |node| node.weight)
+    }
+
+    /// The node ending `word` (normalized as on load), if it is a complete word.
+    fn terminal(&self, word: &str) -> Option<&TrieNode> {
+        let clean_word = self.clean(word)?;
+        #[cfg(feature = "bloom")]
+        if let Some(bloom) = &self.bloom {
+            if !bloom.might_contain(&clean_word) {
+                return None;
+            }
+        }
+        let mut node = &self.root;
+        for ch in clean_word.chars() {
+            node = node.children.get(&ch)?;
+        }
+        node.is_end_of_word.then_some(node)
+    }
+
+    /// All words starting with `prefix`, in alphabetical order.
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.words_with_prefix_limit(prefix, usize::MAX)
+    }
+
+    /// The first `limit` words (alphabetically) starting with `prefix`.
+    ///
+    /// The prefix is trimmed and lowercased; an empty prefix matches every word.
+    pub fn words_with_prefix_limit(&self, prefix: &str, limit: usize) -> Vec<String> {
+        fn collect(node: &TrieNode, word: &mut String, limit: usize, out: &mut Vec<String>) {
+            if out.len() >= limit {
+                return;
+            }
+            if node.is_end_of_word {
+                out.push(word.clone());
+            }
+            let mut children: Vec<_> = node.children.iter().collect();
+            children.sort_by_key(|(ch, _)| **ch);
+            for (ch, child) in children {
+                word.push(*ch);
+                collect(child, word, limit, out);
+                word.pop();
+            }
+        }
+
+        let mut word = prefix.trim().to_lowercase();
+        let mut node = &self.root;
+        for ch in word.chars() {
+            match node.children.get(&ch) {
+                Some(next) => node = next,
+                None => return Vec::new(),
+            }
+        }
+
+        let mut out = Vec::new();
+        collect(node, &mut word, limit, &mut out);
+        out
+    }
+
+    /// Walk the trie and report word and node counts.
+    pub fn stats(&self) -> DictionaryStats {
+        fn walk(node: &TrieNode, depth: usize, stats: &mut DictionaryStats) {
+            stats.node_count += 1;
+            stats.max_depth = stats.max_depth.max(depth);
+            if node.is_end_of_word {
+                stats.word_count += 1;
+                stats.longest_word_len = stats.longest_word_len.max(depth);
+            }
+            for child in node.children.values() {
+                walk(child, depth + 1, stats);
+            }
+        }
+
+        let mut stats = DictionaryStats {
+            word_count: 0,
+            node_count: 0,
+            max_depth: 0,
+            longest_word_len: 0,
+        };
+        walk(&self.root, 0, &mut stats);
+        stats
+    }
+
+    /// Number of words in the trie.
+    pub fn word_count(&self) -> usize {
+        self.stats().word_count
+    }
+
+    /// Write the trie to `path` in a compact binary format for fast reloading.
+    pub fn save_compiled<P: AsRef<Path>>(&self, path: P) -> Result<(), SbsError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        bincode::serialize_into(&mut writer, &(COMPILED_MAGIC, COMPILED_VERSION))
+            .and_then(|_| bincode::serialize_into(&mut writer, &self.root))
+            .map_err(|e| SbsError::SerializationError(e.to_string()))
+    }
+
+    /// Load a trie previously written by [`Dictionary::save_compiled`].
+    ///
+    /// Files from a different format version are rejected rather than decoded.
+    pub fn load_compiled<P: AsRef<Path>>(path: P) -> Result<Self, SbsError> {
+        let path_ref = path.as_ref();
+        let mut reader = BufReader::new(File::open(path_ref)?);
+
+        let (magic, version): ([u8; 4], u32) =
+            bincode::deserialize_from(&mut reader).map_err(|_| {
+                SbsError::DictionaryError(format!("{:?} is not a compiled dictionary.", path_ref))
+            })?;
+        if magic != COMPILED_MAGIC {
+            return Err(SbsError::DictionaryError(format!(
+                "{:?} is not a compiled dictionary.",
+                path_ref
+            )));
+        }
+        if version != COMPILED_VERSION {
+            return Err(SbsError::DictionaryError(format!(
+                "Compiled dictionary {:?} has format version {}, expected {}. Rebuild it from the word list.",
+                path_ref, version, COMPILED_VERSION
+            )));
+        }
+
+        let root = bincode::deserialize_from(&mut reader).map_err(|e| {
+            SbsError::DictionaryError(format!("Corrupt compiled dictionary: {}", e))
+        })?;
+        let mut dictionary = Self {
+            root,
+            options: LoadOptions::default(),
+            #[cfg(feature = "bloom")]
+            bloom: None,
+            revision: next_revision(),
+        };
+        dictionary.build_bloom();
+        Ok(dictionary)
     }
 
     // Helper for tests
@@ -65,12 +584,507 @@ impl Dictionary {
         for w in words {
             root.insert(w);
         }
-        Self { root }
+        let mut dictionary = Self {
+            root,
+            options: LoadOptions::default(),
+            #[cfg(feature = "bloom")]
+            bloom: None,
+            revision: next_revision(),
+        };
+        dictionary.build_bloom();
+        dictionary
     }
 }
 
+/// FNV-1a hash of `url`, stable across runs and Rust versions so cached
+/// downloads keep their names.
+#[cfg(feature = "validator")]
+fn url_hash(url: &str) -> u64 {
+    url.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
 impl Default for Dictionary {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::solver::Solver;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    #[test]
+    fn test_from_file_gzip() {
+        let tmp = tempfile::Builder::new()
+            .suffix(".txt.gz")
+            .tempfile()
+            .unwrap();
+        let mut encoder = GzEncoder::new(tmp.reopen().unwrap(), Compression::default());
+        writeln!(encoder, "fade\nFaced\nbead\nzzzz").unwrap();
+        encoder.finish().unwrap();
+
+        let dict = Dictionary::from_file(tmp.path()).expect("gzip dictionary should load");
+        let solver = Solver::new(Config::new().with_letters("abcdefg").with_present("a"));
+        let results = solver.solve(&dict).unwrap();
+
+        assert!(results.contains("fade"));
+        assert!(results.contains("faced"));
+        assert!(!results.contains("zzzz"));
+    }
+
+    #[test]
+    fn test_from_file_plain_text() {
+        let mut tmp = tempfile::NamedTempFile::new().unwrap();
+        writeln!(tmp, "fade\nbead").unwrap();
+
+        let dict = Dictionary::from_file(tmp.path()).unwrap();
+        let solver = Solver::new(Config::new().with_letters("abcdefg").with_present("a"));
+        let results = solver.solve(&dict).unwrap();
+
+        assert!(results.contains("fade"));
+        assert!(results.contains("bead"));
+    }
+
+    #[test]
+    fn test_from_reader_cursor() {
+        let source = "fade\n  Bead  \n\nwell-being\nzzzz\n";
+        let dict = Dictionary::from_reader(std::io::Cursor::new(source)).unwrap();
+        let solver = Solver::new(Config::new().with_letters("abcdefg").with_present("a"));
+        let results = solver.solve(&dict).unwrap();
+
+        assert!(results.contains("fade"));
+        assert!(results.contains("bead"), "trimmed and lowercased");
+        assert_eq!(results.len(), 2);
+    }
+
+    /// Answer one HTTP request with `status` and `body`; further connections
+    /// are refused.
+    #[cfg(feature = "validator")]
+    fn serve_once(status: u16, body: &'static str) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/words.txt", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0u8; 1024]);
+            let reply = format!(
+                "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(reply.as_bytes());
+        });
+        url
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn test_from_url_downloads_and_caches() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let url = serve_once(200, "fade\nBead\nzzzz\n");
+
+        let dict =
+            Dictionary::from_url_with_options(&url, cache_dir.path(), LoadOptions::default())
+                .unwrap();
+        assert!(dict.contains("fade"));
+        assert!(dict.contains("bead"));
+        assert_eq!(dict.word_count(), 3);
+
+        // The server is gone, so this load can only come from the cache.
+        let cached =
+            Dictionary::from_url_with_options(&url, cache_dir.path(), LoadOptions::default())
+                .unwrap();
+        assert_eq!(cached.word_count(), 3);
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn test_from_url_reports_http_errors() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let url = serve_once(404, "");
+
+        let result =
+            Dictionary::from_url_with_options(&url, cache_dir.path(), LoadOptions::default());
+        let message = result.err().expect("404 is an error").to_string();
+        assert!(message.contains("404"), "{}", message);
+        assert_eq!(std::fs::read_dir(cache_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_from_reader_frequency_column() {
+        let source = "fade\t120\nbead\nfaced\t 7 \ncafe\tnoun\nfade\t5\n";
+        let dict = Dictionary::from_reader(std::io::Cursor::new(source)).unwrap();
+
+        assert_eq!(dict.weight("fade"), 120, "highest duplicate weight wins");
+        assert_eq!(dict.weight("faced"), 7);
+        assert_eq!(dict.weight("bead"), 0, "no column loads as weight 0");
+        assert!(dict.contains("bead"));
+        assert!(!dict.contains("cafe"), "non-numeric column is not a word");
+        assert_eq!(dict.weight("missing"), 0);
+        assert_eq!(dict.word_count(), 3);
+    }
+
+    #[test]
+    fn test_remove_word() {
+        let mut dict = Dictionary::from_words(&["bead", "beads", "bed", "cafe"]);
+
+        assert!(dict.remove_word("bead"));
+        assert!(!dict.contains("bead"));
+        assert!(
+            dict.contains("beads"),
+            "longer words through the prefix survive"
+        );
+        assert!(dict.contains("bed"));
+        assert!(!dict.remove_word("bead"), "already removed");
+        assert!(!dict.remove_word("be"), "a prefix that is not a word");
+        assert!(!dict.remove_word("zebra"));
+        assert!(dict.remove_word("Cafe"), "normalized like loaded words");
+        assert_eq!(dict.word_count(), 2);
+    }
+
+    #[cfg(feature = "bloom")]
+    #[test]
+    fn test_bloom_has_no_false_negatives() {
+        let words: Vec<String> = (0..2000)
+            .map(|i| format!("{:011b}", i).replace('0', "a").replace('1', "b"))
+            .collect();
+        let list = words.join("\n");
+        let dict = Dictionary::from_reader(list.as_bytes()).unwrap();
+        assert!(dict.bloom.is_some(), "built at load");
+        assert!(words.iter().all(|word| dict.contains(word)));
+        assert!(!dict.contains("abc"));
+        assert!(!dict.contains("bbbbbbbbbbbb"));
+    }
+
+    #[cfg(feature = "bloom")]
+    #[test]
+    fn test_bloom_tracks_inserted_and_removed_words() {
+        let mut dict = Dictionary::from_words(&["apple", "leap"]);
+        dict.insert_word("pale");
+        assert!(dict.contains("pale"), "insertions after load are added");
+        assert!(dict.remove_word("leap"));
+        assert!(!dict.contains("leap"), "the trie confirms removals");
+        assert!(dict.contains("apple"));
+    }
+
+    #[test]
+    fn test_revision_changes_with_contents() {
+        let mut dict = Dictionary::from_words(&["apple"]);
+        let other = Dictionary::from_words(&["apple"]);
+        assert_ne!(dict.revision(), other.revision());
+
+        let before = dict.revision();
+        dict.insert_word("leap");
+        let after_insert = dict.revision();
+        assert_ne!(before, after_insert);
+        assert!(!dict.remove_word("pale"));
+        assert_eq!(dict.revision(), after_insert, "unchanged by a no-op");
+        assert!(dict.remove_word("leap"));
+        assert_ne!(dict.revision(), after_insert);
+    }
+
+    #[test]
+    fn test_remove_word_prunes_empty_branches() {
+        let mut dict = Dictionary::from_words(&["bed", "bedded", "cafe"]);
+        let nodes_before = dict.stats().node_count;
+
+        assert!(dict.remove_word("bedded"));
+        assert_eq!(dict.stats().node_count, nodes_before - 3, "d-e-d pruned");
+        assert!(dict.contains("bed"));
+
+        assert!(dict.remove_word("cafe"));
+        assert!(!dict.root.children.contains_key(&'c'));
+
+        dict.insert_word("cafe");
+        assert!(dict.contains("cafe"), "removed words can be re-added");
+    }
+
+    #[test]
+    fn test_overlong_lines_are_skipped() {
+        let huge = "a".repeat(5_000_000);
+        let source = format!("fade\n{}\nbead\n{}\t3\ndecaf", huge, "b".repeat(65));
+        let dict = Dictionary::from_reader(std::io::Cursor::new(source)).unwrap();
+
+        assert_eq!(dict.word_count(), 3);
+        assert!(dict.contains("fade") && dict.contains("bead") && dict.contains("decaf"));
+        assert_eq!(dict.stats().max_depth, 5, "no trie path for the long lines");
+    }
+
+    #[test]
+    fn test_max_word_length_option() {
+        let options = LoadOptions {
+            max_word_length: 4,
+            ..Default::default()
+        };
+        let source = "fade\nfaced\nbead\t9\n";
+        let dict =
+            Dictionary::from_reader_with_options(std::io::Cursor::new(source), options).unwrap();
+
+        assert!(dict.contains("fade"));
+        assert!(!dict.contains("faced"), "5 characters exceeds the limit");
+        assert_eq!(
+            dict.weight("bead"),
+            9,
+            "the frequency column does not count"
+        );
+        assert_eq!(
+            LoadOptions::default().max_word_length,
+            DEFAULT_MAX_WORD_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_compiled_roundtrip() {
+        let dict = Dictionary::from_words(&["fade", "faced", "bead"]);
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        dict.save_compiled(tmp.path()).unwrap();
+
+        let loaded = Dictionary::load_compiled(tmp.path()).unwrap();
+        let solver = Solver::new(Config::new().with_letters("abcdefg").with_present("a"));
+        assert_eq!(solver.solve(&loaded).unwrap(), solver.solve(&dict).unwrap());
+    }
+
+    #[test]
+    fn test_compiled_rejects_stale_version() {
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let header = bincode::serialize(&(COMPILED_MAGIC, COMPILED_VERSION + 1)).unwrap();
+        std::fs::write(tmp.path(), header).unwrap();
+
+        let err = Dictionary::load_compiled(tmp.path()).err().unwrap();
+        assert!(matches!(err, SbsError::DictionaryError(_)));
+        assert!(err.to_string().contains("format version"));
+    }
+
+    #[test]
+    fn test_compiled_rejects_plain_text() {
+        let mut tmp = tempfile::NamedTempFile::new().unwrap();
+        writeln!(tmp, "fade\nbead").unwrap();
+
+        let err = Dictionary::load_compiled(tmp.path()).err().unwrap();
+        assert!(matches!(err, SbsError::DictionaryError(_)));
+    }
+
+    #[test]
+    fn test_from_files_merges_word_lists() {
+        let mut base = tempfile::NamedTempFile::new().unwrap();
+        writeln!(base, "fade\nbead").unwrap();
+        let mut extra = tempfile::NamedTempFile::new().unwrap();
+        writeln!(extra, "faced\nfade").unwrap();
+
+        let dict = Dictionary::from_files(&[base.path(), extra.path()]).unwrap();
+        let solver = Solver::new(Config::new().with_letters("abcdefg").with_present("a"));
+        let results = solver.solve(&dict).unwrap();
+
+        assert!(results.contains("bead"), "from the first file");
+        assert!(results.contains("faced"), "from the second file");
+        assert_eq!(results.len(), 3);
+    }
+
+    #[test]
+    fn test_from_files_reports_failing_path() {
+        let base = tempfile::NamedTempFile::new().unwrap();
+        let missing = Path::new("/nonexistent/slang.txt");
+
+        let err = Dictionary::from_files(&[base.path(), missing])
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("slang.txt"));
+    }
+
+    #[test]
+    fn test_insert_word() {
+        let mut dict = Dictionary::from_words(&["fade"]);
+        dict.insert_word("  Decaf ");
+        dict.insert_word("not-a-word");
+
+        let solver = Solver::new(Config::new().with_letters("abcdefg").with_present("a"));
+        let results = solver.solve(&dict).unwrap();
+
+        assert!(results.contains("decaf"));
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_contains() {
+        let mut dict = Dictionary::from_words(&["apple"]);
+        dict.insert_word("Paris");
+
+        assert!(dict.contains("apple"));
+        assert!(dict.contains(" APPLE "), "normalized like loaded words");
+        assert!(dict.contains("paris"));
+        assert!(dict.contains("Paris"));
+        assert!(!dict.contains("ap"), "prefix but not a word");
+        assert!(!dict.contains("apples"));
+        assert!(!dict.contains("banana"));
+        assert!(!dict.contains(""));
+        assert!(!dict.contains("app-le"));
+    }
+
+    #[test]
+    fn test_words_with_prefix() {
+        let dict = Dictionary::from_words(&["apple", "apply", "ape", "bat", "applesauce"]);
+
+        assert_eq!(
+            dict.words_with_prefix("app"),
+            vec!["apple", "applesauce", "apply"]
+        );
+        assert_eq!(
+            dict.words_with_prefix("Apple"),
+            vec!["apple", "applesauce"],
+            "prefix that is itself a word"
+        );
+        assert!(dict.words_with_prefix("cat").is_empty());
+        assert_eq!(dict.words_with_prefix("").len(), 5);
+    }
+
+    #[test]
+    fn test_words_with_prefix_limit() {
+        let dict = Dictionary::from_words(&["apple", "apply", "ape", "applesauce"]);
+
+        assert_eq!(dict.words_with_prefix_limit("ap", 2), vec!["ape", "apple"]);
+        assert!(dict.words_with_prefix_limit("ap", 0).is_empty());
+    }
+
+    #[test]
+    fn test_stats_counts() {
+        let dict = Dictionary::from_words(&["a", "ab", "abc", "b", "bad"]);
+        let stats = dict.stats();
+
+        assert_eq!(
+            stats,
+            DictionaryStats {
+                word_count: 5,
+                node_count: 7,
+                max_depth: 3,
+                longest_word_len: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_word_count() {
+        assert_eq!(Dictionary::from_words(&["a", "ab", "abc"]).word_count(), 3);
+        assert_eq!(Dictionary::new().word_count(), 0);
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        let stats = Dictionary::new().stats();
+        assert_eq!(stats.word_count, 0);
+        assert_eq!(stats.node_count, 1, "root only");
+        assert_eq!(stats.max_depth, 0);
+    }
+
+    #[test]
+    fn test_original_case_lowercase_entry_wins() {
+        let source = "Polish\npolish\nParis\n";
+        let dict = Dictionary::from_reader(std::io::Cursor::new(source)).unwrap();
+
+        let polish = &dict.root.children[&'p'].children[&'o'].children[&'l'].children[&'i']
+            .children[&'s']
+            .children[&'h'];
+        assert!(polish.is_end_of_word);
+        assert_eq!(polish.original, None);
+
+        let paris =
+            &dict.root.children[&'p'].children[&'a'].children[&'r'].children[&'i'].children[&'s'];
+        assert_eq!(paris.original.as_deref(), Some("Paris"));
+    }
+
+    #[test]
+    fn test_punctuation_rejected_by_default() {
+        let source = "well-being\no'clock\nbeing\n";
+        let dict = Dictionary::from_reader(std::io::Cursor::new(source)).unwrap();
+        assert_eq!(dict.stats().word_count, 1);
+    }
+
+    #[test]
+    fn test_punctuation_allowed_inside_words() {
+        let source = "well-being\no'clock\n-dash\ntrailing'\n'\n";
+        let options = LoadOptions {
+            allow_punctuation: true,
+            ..Default::default()
+        };
+        let mut dict =
+            Dictionary::from_reader_with_options(std::io::Cursor::new(source), options).unwrap();
+        assert_eq!(dict.stats().word_count, 2, "only inner connectors are kept");
+
+        dict.insert_word("rock-n-roll");
+        assert_eq!(
+            dict.stats().word_count,
+            3,
+            "insert_word honours the options"
+        );
+    }
+
+    fn folding_options() -> LoadOptions {
+        LoadOptions {
+            fold_accents: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fold_accents_indexes_plain_form() {
+        let source = "café
+naïve
+Crème
+fiancée
+";
+        let dict =
+            Dictionary::from_reader_with_options(std::io::Cursor::new(source), folding_options())
+                .unwrap();
+
+        assert!(dict.contains("cafe"));
+        assert!(dict.contains("naive"));
+        assert!(dict.contains("creme"));
+        assert!(dict.contains("café"), "queries are folded too");
+
+        let solver = Solver::new(Config::new().with_letters("acefin").with_present("a"));
+        let results = solver.solve(&dict).unwrap();
+        assert!(results.contains("cafe"));
+        assert!(results.contains("fiancee"));
+        assert!(!results.contains("naive"), "no v among the letters");
+    }
+
+    #[test]
+    fn test_fold_accents_decomposes_compatibility_forms() {
+        let mut dict = Dictionary::with_options(folding_options());
+        // Precomposed é and e + U+0301 combining acute fold to the same word
+        dict.insert_word("caf\u{e9}");
+        dict.insert_word("cafe\u{301}s");
+        dict.insert_word("\u{fb01}ne"); // "ﬁ" ligature
+        assert_eq!(dict.word_count(), 3);
+        assert!(dict.contains("cafes"));
+        assert!(dict.contains("fine"));
+    }
+
+    #[test]
+    fn test_fold_accents_keeps_original_spelling() {
+        let mut dict = Dictionary::with_options(folding_options());
+        dict.insert_word("Café");
+
+        let mut config = Config::new().with_letters("acef").with_present("a");
+        config.preserve_case = Some(true);
+        let results = Solver::new(config).solve(&dict).unwrap();
+        assert!(results.contains("Café"));
+    }
+
+    #[test]
+    fn test_accents_kept_without_folding() {
+        let dict = Dictionary::from_reader(std::io::Cursor::new(
+            "café
+",
+        ))
+        .unwrap();
+        assert!(!dict.contains("cafe"));
+        assert!(dict.contains("café"));
+    }
+}
@@ -10,6 +10,92 @@ pub enum SbsError {
     IoError(#[from] std::io::Error),
     #[error("Serialization error: {0}")]
     SerializationError(String),
+    /// Malformed input text, with the 1-based position of the problem when known.
+    #[error("Parse error: {message}")]
+    ParseError {
+        message: String,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
     #[error("Validation error: {0}")]
     ValidationError(String),
+    #[error("Timed out: {0}")]
+    Timeout(String),
+}
+
+impl From<serde_json::Error> for SbsError {
+    fn from(e: serde_json::Error) -> Self {
+        // serde_json reports line 0 for errors not tied to a position (e.g. I/O)
+        let known = e.line() > 0;
+        SbsError::ParseError {
+            message: e.to_string(),
+            line: known.then(|| e.line()),
+            column: known.then(|| e.column()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_each_variant() {
+        let cases = [
+            (
+                SbsError::ConfigError("no letters".to_string()),
+                "Configuration error: no letters",
+            ),
+            (
+                SbsError::DictionaryError("missing".to_string()),
+                "Dictionary error: missing",
+            ),
+            (
+                SbsError::IoError(std::io::Error::new(std::io::ErrorKind::NotFound, "gone")),
+                "IO error: gone",
+            ),
+            (
+                SbsError::SerializationError("bad bytes".to_string()),
+                "Serialization error: bad bytes",
+            ),
+            (
+                SbsError::ParseError {
+                    message: "unexpected token".to_string(),
+                    line: Some(2),
+                    column: Some(5),
+                },
+                "Parse error: unexpected token",
+            ),
+            (
+                SbsError::ValidationError("HTTP 500".to_string()),
+                "Validation error: HTTP 500",
+            ),
+            (
+                SbsError::Timeout("solve deadline passed".to_string()),
+                "Timed out: solve deadline passed",
+            ),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(error.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        let error: SbsError = io.into();
+        assert!(matches!(error, SbsError::IoError(_)));
+    }
+
+    #[test]
+    fn test_from_serde_json_error() {
+        let json_error = serde_json::from_str::<serde_json::Value>("[1,\n2,").unwrap_err();
+        match SbsError::from(json_error) {
+            SbsError::ParseError { line, column, .. } => {
+                assert_eq!(line, Some(2));
+                assert_eq!(column, Some(2));
+            }
+            other => panic!("expected ParseError, got {:?}", other),
+        }
+    }
 }
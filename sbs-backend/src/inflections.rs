@@ -0,0 +1,127 @@
+//! Heuristic grouping of inflected results under their stems.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Suffixes stripped when looking for a stem, longest first.
+const SUFFIXES: [&str; 4] = ["ing", "es", "ed", "s"];
+
+/// A stem together with every result that collapses onto it (stem included).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InflectionGroup {
+    pub stem: String,
+    pub forms: Vec<String>,
+}
+
+/// Collapse inflected forms (`-s`, `-es`, `-ed`, `-ing`) under their stem.
+///
+/// A word is only grouped when its candidate stem is itself among `words`,
+/// so "bus" stays on its own unless "bu" is a result. Handles a doubled final
+/// consonant ("running" -> "run") and a dropped `e` ("baked" -> "bake").
+/// Groups are sorted by stem and forms alphabetically.
+pub fn group_inflections(words: &[String]) -> Vec<InflectionGroup> {
+    let known: HashSet<&str> = words.iter().map(String::as_str).collect();
+
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for word in words {
+        groups
+            .entry(root(word, &known))
+            .or_default()
+            .push(word.clone());
+    }
+
+    groups
+        .into_iter()
+        .map(|(stem, mut forms)| {
+            forms.sort();
+            forms.dedup();
+            InflectionGroup { stem, forms }
+        })
+        .collect()
+}
+
+/// Follow stems until reaching a word with no known stem of its own.
+fn root(word: &str, known: &HashSet<&str>) -> String {
+    let mut current = word.to_string();
+    while let Some(stem) = stem(&current, known) {
+        current = stem;
+    }
+    current
+}
+
+/// The first known stem of `word`, if any suffix rule yields one.
+fn stem(word: &str, known: &HashSet<&str>) -> Option<String> {
+    SUFFIXES.iter().find_map(|suffix| {
+        let base = word.strip_suffix(suffix).filter(|b| !b.is_empty())?;
+        candidates(base, suffix)
+            .into_iter()
+            .find(|candidate| known.contains(candidate.as_str()))
+    })
+}
+
+/// Possible stems for `base`, the word with `suffix` removed.
+fn candidates(base: &str, suffix: &str) -> Vec<String> {
+    let mut candidates = vec![base.to_string()];
+    if matches!(suffix, "ed" | "ing") {
+        candidates.push(format!("{}e", base));
+        let mut chars = base.chars().rev();
+        if let (Some(last), Some(previous)) = (chars.next(), chars.next()) {
+            if last == previous {
+                candidates.push(base[..base.len() - last.len_utf8()].to_string());
+            }
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn group<'a>(groups: &'a [InflectionGroup], stem: &str) -> &'a [String] {
+        &groups.iter().find(|g| g.stem == stem).unwrap().forms
+    }
+
+    #[test]
+    fn test_groups_common_suffixes() {
+        let groups = group_inflections(&words(&[
+            "run", "runs", "running", "box", "boxes", "walk", "walked", "walking",
+        ]));
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(group(&groups, "run"), ["run", "running", "runs"]);
+        assert_eq!(group(&groups, "box"), ["box", "boxes"]);
+        assert_eq!(group(&groups, "walk"), ["walk", "walked", "walking"]);
+    }
+
+    #[test]
+    fn test_groups_dropped_e() {
+        let groups = group_inflections(&words(&["bake", "baked", "baking", "bakes"]));
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(group(&groups, "bake"), ["bake", "baked", "bakes", "baking"]);
+    }
+
+    #[test]
+    fn test_requires_stem_among_results() {
+        let groups = group_inflections(&words(&["bus", "gas", "ring", "sing", "singing"]));
+
+        assert_eq!(group(&groups, "bus"), ["bus"], "bu is not a result");
+        assert_eq!(group(&groups, "gas"), ["gas"]);
+        assert_eq!(group(&groups, "ring"), ["ring"], "r is not a result");
+        assert_eq!(group(&groups, "sing"), ["sing", "singing"]);
+    }
+
+    #[test]
+    fn test_follows_chained_stems() {
+        let groups = group_inflections(&words(&["box", "boxing", "boxings"]));
+
+        // "boxings" -> "boxing" -> "box"
+        assert_eq!(groups.len(), 1);
+        assert_eq!(group(&groups, "box"), ["box", "boxing", "boxings"]);
+    }
+}
@@ -1,18 +1,26 @@
 //! Core library for the Spelling Bee Solver.
 
+#[cfg(feature = "bloom")]
+mod bloom;
 pub mod config;
 pub mod dictionary;
 pub mod error;
+pub mod inflections;
 pub mod solver;
 #[cfg(feature = "validator")]
 pub mod validator;
 
-pub use config::Config;
-pub use dictionary::Dictionary;
+pub use config::{Config, PuzzleMode, RepeatPolicy, SortOrder};
+pub use dictionary::{Dictionary, DictionaryStats, LoadOptions};
 pub use error::SbsError;
-pub use solver::Solver;
+pub use inflections::{group_inflections, InflectionGroup};
+pub use solver::{
+    CachingSolver, SolveResult, SolveSummary, SolvedWord, Solver, DEFAULT_SOLVE_CACHE_CAPACITY,
+};
 #[cfg(feature = "validator")]
 pub use validator::{
-    create_validator, CustomValidator, FreeDictionaryValidator, MerriamWebsterValidator,
-    ValidationSummary, Validator, ValidatorKind, WordEntry, WordnikValidator,
+    create_validator, create_validator_with_timeout, CachingValidator, ChainValidator,
+    CustomValidator, DatamuseValidator, FreeDictionaryValidator, HttpSettings, HttpValidator,
+    LookupOutcome, MerriamWebsterValidator, OfflineValidator, ValidationSummary, Validator,
+    ValidatorKind, WordEntry, WordnikValidator, DEFAULT_HTTP_TIMEOUT, SELF_TEST_WORD,
 };
@@ -1,19 +1,27 @@
 //! CLI entry point for Spelling Bee Solver.
 
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use clap_complete::Shell;
 #[cfg(feature = "validator")]
-use sbs::{create_validator, ValidatorKind};
-use sbs::{Config, Dictionary, Solver};
+use sbs::{create_validator_with_timeout, ValidatorKind, DEFAULT_HTTP_TIMEOUT, SELF_TEST_WORD};
+use sbs::{
+    Config, Dictionary, DictionaryStats, InflectionGroup, PuzzleMode, SolveResult, Solver,
+    SortOrder,
+};
+use std::collections::BTreeMap;
 use std::fs::File;
-use std::io::Write;
+use std::io::{BufRead, Write};
 use std::path::PathBuf;
 use std::process;
+#[cfg(feature = "validator")]
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(name = "sbs")]
 #[command(version)]
 #[command(disable_version_flag = true)]
 #[command(about = "Spelling Bee Solver tool", long_about = None)]
+#[command(after_help = "Shell completion: source <(sbs --completions bash)")]
 struct Args {
     #[arg(
         short = 'a',
@@ -29,38 +37,177 @@ struct Args {
         alias = "present"
     )]
     required_letters: Option<String>,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Accept words containing at least N of the required letters instead of all"
+    )]
+    required_min: Option<usize>,
     #[arg(short, long)]
     config: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Read \"letters [present]\" from one line of stdin; flags take precedence"
+    )]
+    stdin: bool,
     #[arg(short, long)]
     dictionary: Option<PathBuf>,
+    #[cfg(feature = "validator")]
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "Download the word list from URL (cached locally) instead of reading --dictionary"
+    )]
+    dictionary_url: Option<String>,
     #[arg(short, long)]
     output: Option<String>,
     #[cfg(feature = "validator")]
     #[arg(
         long,
-        help = "Validator: free-dictionary, merriam-webster, wordnik, custom"
+        help = "Validator: free-dictionary, merriam-webster, wordnik, datamuse, custom, offline"
     )]
     validator: Option<String>,
     #[cfg(feature = "validator")]
     #[arg(long, help = "API key for validators that require one")]
     api_key: Option<String>,
     #[cfg(feature = "validator")]
-    #[arg(long, help = "Custom validator URL (use with --validator custom)")]
+    #[arg(
+        long,
+        help = "Custom validator URL (--validator custom) or word list path (--validator offline)"
+    )]
     validator_url: Option<String>,
-    #[arg(long)]
+    #[cfg(feature = "validator")]
+    #[arg(
+        long,
+        value_name = "SECS",
+        help = "HTTP timeout for validator requests in seconds (default 10)"
+    )]
+    validator_timeout: Option<u64>,
+    #[cfg(feature = "validator")]
+    #[arg(
+        long,
+        help = "Look up one known word with the validator to check its endpoint and API key, then exit"
+    )]
+    validate_check: bool,
+    #[arg(long, alias = "min-length")]
     minimal_word_length: Option<usize>,
-    #[arg(long)]
+    #[arg(long, alias = "max-length")]
     maximal_word_length: Option<usize>,
     #[arg(
         long,
         default_value = "plain",
-        help = "Output format: plain, json, markdown"
+        help = "Output format: plain, json, markdown, csv, tsv"
     )]
     format: String,
     #[arg(long)]
     case_sensitive: bool,
+    #[arg(
+        long,
+        help = "Regex results must match (unanchored; use ^...$), or a template like c__e"
+    )]
+    pattern: Option<String>,
+    #[arg(long, help = "Prefix every result must start with")]
+    starts_with: Option<String>,
+    #[arg(long, help = "Suffix every result must end with")]
+    ends_with: Option<String>,
+    #[arg(long, help = "Substring every result must contain")]
+    contains: Option<String>,
+    #[arg(long, help = "Letters that must not appear in any result")]
+    excluded: Option<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Never output words listed in PATH (one per line)"
+    )]
+    blocklist_file: Option<PathBuf>,
+    #[arg(long, help = "Accept dictionary words containing '-' or '\''")]
+    allow_punctuation: bool,
+    #[arg(
+        long,
+        help = "Match accented dictionary words by their plain letters (café as cafe)"
+    )]
+    fold_accents: bool,
     #[arg(long)]
     about: bool,
+    #[arg(long, help = "Print the JSON Schema for config files and exit")]
+    schema: bool,
+    #[arg(
+        long,
+        value_name = "SHELL",
+        help = "Print a completion script for bash, zsh, fish, or powershell"
+    )]
+    completions: Option<Shell>,
+    #[arg(
+        long,
+        value_name = "WORD",
+        help = "Print yes if WORD is in the dictionary, no otherwise (exit status 1)"
+    )]
+    check: Option<String>,
+    #[arg(
+        long,
+        value_name = "PREFIX",
+        help = "List dictionary words starting with PREFIX instead of solving"
+    )]
+    prefix: Option<String>,
+    #[arg(
+        long,
+        value_name = "WORD",
+        help = "List dictionary words within two edits of WORD (did you mean?) instead of solving"
+    )]
+    suggest: Option<String>,
+    #[arg(long, help = "Print dictionary statistics instead of solving")]
+    dict_stats: bool,
+    #[arg(long, help = "Only output pangrams (words using every letter)")]
+    pangrams_only: bool,
+    #[arg(
+        long,
+        help = "Only output perfect pangrams (every letter exactly once)"
+    )]
+    perfect_pangrams_only: bool,
+    #[arg(
+        long,
+        help = "List pangrams first, marked with '*' (plain) or a heading (markdown)"
+    )]
+    pangrams_first: bool,
+    #[arg(
+        long,
+        help = "Sort order: alpha (default), length, length-desc, score, frequency"
+    )]
+    sort: Option<String>,
+    #[arg(
+        long,
+        help = "Puzzle rules: classic (default) or nyt (one center letter, min length 4, pangrams first)"
+    )]
+    mode: Option<String>,
+    #[arg(long, help = "Print only the number of results")]
+    count: bool,
+    #[arg(
+        long,
+        help = "Collapse inflected forms (-s, -es, -ed, -ing) under their stem"
+    )]
+    group_inflections: bool,
+    #[arg(long, help = "Print a bar chart of result counts by word length")]
+    histogram: bool,
+    #[arg(
+        long,
+        help = "Print the two-letter hint grid: result counts by first two letters"
+    )]
+    hints: bool,
+    #[arg(
+        long,
+        help = "Print only the rarest result: least frequent, else longest with the rarest letters"
+    )]
+    rarest: bool,
+    #[arg(
+        long,
+        help = "Also list words one letter short of the minimum (or from bonus-min-length) in a separate Bonus section"
+    )]
+    bonus: bool,
+    #[arg(
+        long,
+        help = "Load the dictionary once and read commands (letters, present, min, max, solve, quit)"
+    )]
+    interactive: bool,
 }
 
 fn print_about() {
@@ -72,12 +219,169 @@ fn print_about() {
     println!("└─ usage:     sbs --help");
 }
 
+fn print_dict_stats(paths: &[PathBuf], stats: &DictionaryStats) {
+    let names: Vec<_> = paths.iter().map(|p| p.display().to_string()).collect();
+    println!("dictionary: {}", names.join(", "));
+    println!("├─ words:        {}", stats.word_count);
+    println!("├─ nodes:        {}", stats.node_count);
+    println!("├─ max depth:    {}", stats.max_depth);
+    println!("└─ longest word: {}", stats.longest_word_len);
+}
+
+/// Load the configured word list, downloading it when a URL is set and
+/// merging every file when `dictionaries` lists several.
+fn load_dictionary(config: &Config) -> Result<Dictionary, sbs::SbsError> {
+    #[cfg(feature = "validator")]
+    if let Some(url) = &config.dictionary_url {
+        let cache_dir = std::env::temp_dir().join("sbs-dictionaries");
+        return Dictionary::from_url_with_options(url, cache_dir, config.load_options());
+    }
+    if let Some(paths) = &config.dictionaries {
+        return Dictionary::from_files_with_options(paths, config.load_options());
+    }
+    Dictionary::from_file_with_options(&config.dictionary, config.load_options())
+}
+
+/// Edit distance searched by `--suggest`.
+const SUGGEST_DISTANCE: usize = 2;
+
+const INTERACTIVE_HELP: &str = "Commands: letters <abc>, present <a>, min <n>, max <n|none>, \
+show, solve, help, quit";
+
+/// Read commands from `input` until `quit` or end of input, solving against
+/// the already-loaded dictionary. Prompts go to stderr; results to `output`.
+fn run_interactive<R: BufRead, W: Write>(
+    input: R,
+    mut output: W,
+    dictionary: &Dictionary,
+    mut config: Config,
+    format: &str,
+) -> std::io::Result<()> {
+    let prompt = || {
+        eprint!("sbs> ");
+        let _ = std::io::stderr().flush();
+    };
+    prompt();
+    for line in input.lines() {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+        let command = tokens.next().unwrap_or("");
+        let value = tokens.next();
+        let parse_len = |v: Option<&str>| v.and_then(|n| n.parse::<usize>().ok());
+        match (command, value) {
+            ("", _) => {}
+            ("quit" | "exit", _) => return Ok(()),
+            ("help", _) => writeln!(output, "{}", INTERACTIVE_HELP)?,
+            ("letters", Some(v)) => config.letters = Some(v.to_string()),
+            ("present", Some(v)) => config.present = Some(v.to_string()),
+            ("min", v) if parse_len(v).is_some() => config.minimal_word_length = parse_len(v),
+            ("max", Some("none")) => config.maximal_word_length = None,
+            ("max", v) if parse_len(v).is_some() => config.maximal_word_length = parse_len(v),
+            ("show", _) => writeln!(
+                output,
+                "letters: {}, present: {}, min: {}, max: {}",
+                config.letters.as_deref().unwrap_or("-"),
+                config.present.as_deref().unwrap_or("-"),
+                config
+                    .minimal_word_length
+                    .map_or("-".to_string(), |n| n.to_string()),
+                config
+                    .maximal_word_length
+                    .map_or("-".to_string(), |n| n.to_string()),
+            )?,
+            ("solve", _) if config.letters.is_none() => {
+                writeln!(output, "Error: letters are required.")?
+            }
+            ("solve", _) => match config
+                .validate()
+                .and_then(|()| Solver::from_config_ref(&config).solve_result(dictionary))
+            {
+                Ok(result) => {
+                    writeln!(output, "{}", format_result(&result, format, 0))?;
+                    eprintln!("Generated {} words.", result.count);
+                }
+                Err(e) => writeln!(output, "Error: {}", e)?,
+            },
+            _ => writeln!(
+                output,
+                "Unknown command '{}'. {}",
+                line.trim(),
+                INTERACTIVE_HELP
+            )?,
+        }
+        prompt();
+    }
+    eprintln!();
+    Ok(())
+}
+
+/// Read `letters [present]` from the first line of `reader`.
+fn read_puzzle<R: BufRead>(mut reader: R) -> Result<(String, Option<String>), String> {
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .map_err(|e| format!("failed to read stdin: {}", e))?;
+    let mut tokens = line.split_whitespace();
+    let letters = tokens
+        .next()
+        .ok_or("no letters on stdin; expected \"letters [present]\"")?;
+    Ok((letters.to_string(), tokens.next().map(str::to_string)))
+}
+
+/// Read a word list: one word per line, ignoring blank lines and `#` comments.
+fn read_word_list<R: BufRead>(reader: R) -> std::io::Result<Vec<String>> {
+    let mut words = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let word = line.trim();
+        if !word.is_empty() && !word.starts_with('#') {
+            words.push(word.to_string());
+        }
+    }
+    Ok(words)
+}
+
+fn print_completions(shell: Shell, out: &mut dyn Write) {
+    let mut command = Args::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
+/// Apply `--dictionary` after merging: unlike a merged config, an explicit
+/// flag wins even when it names the default path, and replaces any
+/// `dictionaries` list from the config file.
+fn apply_dictionary_flag(config: &mut Config, dictionary: Option<PathBuf>) {
+    if let Some(d) = dictionary {
+        config.dictionary = d;
+        config.dictionaries = None;
+    }
+}
+
+/// Parse a CLI option value, exiting with the parse error on failure.
+fn parse_or_exit<T: std::str::FromStr<Err = sbs::SbsError>>(value: &str) -> T {
+    value.parse().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    })
+}
+
 fn main() {
     let args = Args::parse();
     if args.about {
         print_about();
         return;
     }
+    if args.schema {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&Config::json_schema()).unwrap_or_default()
+        );
+        return;
+    }
+    if let Some(shell) = args.completions {
+        print_completions(shell, &mut std::io::stdout());
+        return;
+    }
 
     let mut config = if let Some(path) = args.config {
         match Config::from_file(&path) {
@@ -91,53 +395,123 @@ fn main() {
         Config::default()
     };
 
-    if let Some(l) = args.available_letters {
-        config.letters = Some(l);
-    }
-    if let Some(r) = args.required_letters {
-        config.present = Some(r);
-    }
-    if let Some(d) = args.dictionary {
-        config.dictionary = d;
-    }
-    if let Some(o) = args.output {
-        config.output = Some(o);
-    }
-    if let Some(n) = args.minimal_word_length {
-        config.minimal_word_length = Some(n);
-    }
-    if let Some(n) = args.maximal_word_length {
-        config.maximal_word_length = Some(n);
+    if args.stdin {
+        match read_puzzle(std::io::stdin().lock()) {
+            Ok((letters, present)) => {
+                config.letters = Some(letters);
+                if present.is_some() {
+                    config.present = present;
+                }
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
     }
-    if args.case_sensitive {
-        config.case_sensitive = Some(true);
+    let mut overrides = Config::new();
+    overrides.letters = args.available_letters;
+    overrides.present = args.required_letters;
+    overrides.required_min = args.required_min;
+    overrides.output = args.output;
+    overrides.minimal_word_length = args.minimal_word_length;
+    overrides.maximal_word_length = args.maximal_word_length;
+    overrides.case_sensitive = args.case_sensitive.then_some(true);
+    overrides.pattern = args.pattern;
+    overrides.excluded = args.excluded;
+    overrides.starts_with = args.starts_with;
+    overrides.ends_with = args.ends_with;
+    overrides.group_inflections = args.group_inflections.then_some(true);
+    overrides.contains = args.contains;
+    overrides.allow_punctuation = args.allow_punctuation.then_some(true);
+    overrides.fold_accents = args.fold_accents.then_some(true);
+    overrides.sort_order = args.sort.as_deref().map(parse_or_exit::<SortOrder>);
+    overrides.mode = args.mode.as_deref().map(parse_or_exit::<PuzzleMode>);
+    #[cfg(feature = "validator")]
+    {
+        overrides.dictionary_url = args.dictionary_url;
+        overrides.validator = args
+            .validator
+            .as_deref()
+            .map(parse_or_exit::<ValidatorKind>);
+        overrides.api_key = args.api_key;
+        overrides.validator_url = args.validator_url;
+        overrides.validator_timeout = args.validator_timeout;
     }
+    config.merge(overrides);
+    apply_dictionary_flag(&mut config, args.dictionary);
 
-    // Parse validator from CLI flag
-    #[cfg(feature = "validator")]
-    let validator_kind = if let Some(v) = args.validator {
-        match v.parse::<ValidatorKind>() {
-            Ok(kind) => Some(kind),
+    if args.bonus && config.bonus_min_length.is_none() {
+        config.bonus_min_length = Some(config.min_length().saturating_sub(1));
+    }
+    if let Some(path) = args.blocklist_file {
+        let words = std::fs::File::open(&path)
+            .and_then(|file| read_word_list(std::io::BufReader::new(file)));
+        match words {
+            // Extends any blocklist from the config file
+            Ok(words) => config.blocklist.get_or_insert_with(Vec::new).extend(words),
             Err(e) => {
-                eprintln!("Error: {}", e);
+                eprintln!("Error: cannot read blocklist {}: {}", path.display(), e);
                 process::exit(1);
             }
         }
-    } else {
-        config.validator.clone()
-    };
+    }
 
     #[cfg(feature = "validator")]
-    let api_key = args.api_key.or(config.api_key.clone());
+    let validator_kind = config.validator.clone();
     #[cfg(feature = "validator")]
-    let validator_url = args.validator_url.or(config.validator_url.clone());
+    let api_key = config.api_key.clone();
+    #[cfg(feature = "validator")]
+    let validator_url = config.validator_url.clone();
+    #[cfg(feature = "validator")]
+    let validator_timeout = config
+        .validator_timeout
+        .map_or(DEFAULT_HTTP_TIMEOUT, Duration::from_secs);
+
+    #[cfg(feature = "validator")]
+    if args.validate_check {
+        let Some(kind) = validator_kind else {
+            eprintln!("Error: --validate-check needs a validator (--validator or config).");
+            process::exit(1);
+        };
+        let result = create_validator_with_timeout(
+            &kind,
+            api_key.as_deref(),
+            validator_url.as_deref(),
+            validator_timeout,
+        )
+        .and_then(|validator| validator.self_test());
+        match result {
+            Ok(()) => println!(
+                "ok: {} recognized '{}'",
+                kind.display_name(),
+                SELF_TEST_WORD
+            ),
+            Err(e) => {
+                eprintln!("Validator check failed: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
 
-    if config.letters.is_none() {
+    let solving = !args.dict_stats
+        && !args.interactive
+        && args.check.is_none()
+        && args.prefix.is_none()
+        && args.suggest.is_none();
+    if config.letters.is_none() && solving {
         eprintln!("Error: letters are required.");
         process::exit(1);
     }
+    if solving {
+        if let Err(e) = config.validate() {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
 
-    let dictionary = match Dictionary::from_file(&config.dictionary) {
+    let dictionary = match load_dictionary(&config) {
         Ok(d) => d,
         Err(e) => {
             eprintln!("Dictionary error: {}", e);
@@ -146,45 +520,146 @@ fn main() {
         }
     };
 
-    let solver = Solver::new(config.clone());
+    if args.dict_stats {
+        let paths = config
+            .dictionaries
+            .clone()
+            .unwrap_or_else(|| vec![config.dictionary.clone()]);
+        print_dict_stats(&paths, &dictionary.stats());
+        return;
+    }
+
+    if let Some(word) = args.check {
+        let found = dictionary.contains(&word);
+        println!("{}", if found { "yes" } else { "no" });
+        process::exit(if found { 0 } else { 1 });
+    }
+
+    let solver = Solver::from_config_ref(&config);
+    let highlight_pangrams = args.pangrams_first || config.mode() == PuzzleMode::Nyt;
 
     let format = args.format.as_str();
-    if !matches!(format, "plain" | "json" | "markdown") {
+    if !matches!(format, "plain" | "json" | "markdown" | "csv" | "tsv") {
         eprintln!(
-            "Error: unsupported format '{}'. Use plain, json, or markdown.",
+            "Error: unsupported format '{}'. Use plain, json, markdown, csv, or tsv.",
             format
         );
         process::exit(1);
     }
 
-    match solver.solve(&dictionary) {
-        Ok(words) => {
-            let mut sorted_words: Vec<_> = words.into_iter().collect();
-            sorted_words.sort();
+    if let Some(prefix) = &args.prefix {
+        let words = dictionary.words_with_prefix(prefix);
+        write_output(
+            &format_unvalidated(&words, format, 0),
+            config.output.as_deref(),
+        );
+        return;
+    }
+
+    if let Some(word) = &args.suggest {
+        let words = solver.nearest_words(&dictionary, word, SUGGEST_DISTANCE);
+        write_output(
+            &format_unvalidated(&words, format, 0),
+            config.output.as_deref(),
+        );
+        return;
+    }
+
+    if args.interactive {
+        let stdin = std::io::stdin();
+        if let Err(e) =
+            run_interactive(stdin.lock(), std::io::stdout(), &dictionary, config, format)
+        {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    match solver.solve_with_bonus(&dictionary) {
+        Ok((mut sorted_words, bonus)) => {
+            let is_pangram = |word: &str| solver.word_is_pangram(word);
+            if args.pangrams_only {
+                sorted_words.retain(|w| is_pangram(w));
+            }
+            if args.perfect_pangrams_only {
+                sorted_words.retain(|w| solver.word_is_perfect_pangram(w));
+            }
+            let marked = if highlight_pangrams {
+                pangrams_first(&mut sorted_words, is_pangram)
+            } else {
+                0
+            };
 
             #[cfg(feature = "validator")]
             let validated = if let Some(kind) = validator_kind {
-                let validator =
-                    match create_validator(&kind, api_key.as_deref(), validator_url.as_deref()) {
-                        Ok(v) => v,
-                        Err(e) => {
-                            eprintln!("Validator error: {}", e);
-                            process::exit(1);
-                        }
-                    };
+                let validator = match create_validator_with_timeout(
+                    &kind,
+                    api_key.as_deref(),
+                    validator_url.as_deref(),
+                    validator_timeout,
+                ) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        eprintln!("Validator error: {}", e);
+                        process::exit(1);
+                    }
+                };
 
                 let summary =
                     validator.validate_words_with_progress(&sorted_words, &|done, total| {
                         eprint!("\rValidating: {}/{}", done, total);
                     });
+                if args.count {
+                    eprintln!();
+                    let output = format_count(summary.validated, Some(summary.candidates), format);
+                    write_output(&output, config.output.as_deref());
+                    return;
+                }
+                if args.histogram {
+                    eprintln!();
+                    let words = summary.entries.iter().map(|e| &e.word);
+                    let output = format_histogram(&Solver::count_lengths(words), format);
+                    write_output(&output, config.output.as_deref());
+                    return;
+                }
+                if args.hints {
+                    eprintln!();
+                    let words = summary.entries.iter().map(|e| &e.word);
+                    let output = format_hints(&Solver::count_bigrams(words), format);
+                    write_output(&output, config.output.as_deref());
+                    return;
+                }
+                if args.rarest {
+                    eprintln!();
+                    let words = summary.entries.iter().map(|e| &e.word);
+                    let output = format_rarest(Solver::rarest_of(words, &dictionary), format);
+                    write_output(&output, config.output.as_deref());
+                    return;
+                }
                 eprintln!(
                     "\rGenerated {} candidates, {} validated by {}.",
                     summary.candidates,
                     summary.validated,
                     kind.display_name()
                 );
+                if summary.unavailable > 0 {
+                    eprintln!(
+                        "{} candidates could not be checked due to lookup errors.",
+                        summary.unavailable
+                    );
+                }
 
-                let output = format_validated(&summary.entries, format);
+                let marked = if highlight_pangrams {
+                    summary
+                        .entries
+                        .iter()
+                        .take_while(|e| is_pangram(&e.word))
+                        .count()
+                } else {
+                    0
+                };
+                let output = format_validated(&summary.entries, format, marked);
                 write_output(&output, config.output.as_deref());
                 true
             } else {
@@ -196,9 +671,40 @@ fn main() {
                 return;
             }
 
+            if args.count {
+                let output = format_count(sorted_words.len(), None, format);
+                write_output(&output, config.output.as_deref());
+                return;
+            }
+            if args.histogram {
+                let output = format_histogram(&Solver::count_lengths(&sorted_words), format);
+                write_output(&output, config.output.as_deref());
+                return;
+            }
+            if args.hints {
+                let output = format_hints(&Solver::count_bigrams(&sorted_words), format);
+                write_output(&output, config.output.as_deref());
+                return;
+            }
+            if args.rarest {
+                let output = format_rarest(Solver::rarest_of(&sorted_words, &dictionary), format);
+                write_output(&output, config.output.as_deref());
+                return;
+            }
+
             eprintln!("Generated {} words.", sorted_words.len());
 
-            let output = format_unvalidated(&sorted_words, format);
+            if config.group_inflections.unwrap_or(false) {
+                let output = format_groups(&sbs::group_inflections(&sorted_words), format);
+                write_output(&output, config.output.as_deref());
+                return;
+            }
+
+            let result = SolveResult {
+                bonus,
+                ..solver.result_from_words(sorted_words)
+            };
+            let output = format_result(&result, format, marked);
             write_output(&output, config.output.as_deref());
         }
         Err(e) => {
@@ -208,30 +714,243 @@ fn main() {
     }
 }
 
-fn format_unvalidated(words: &[String], format: &str) -> String {
+/// Render a result count; with validation, `candidates` is the pre-validation total.
+fn format_count(count: usize, candidates: Option<usize>, format: &str) -> String {
+    match (format, candidates) {
+        ("json", None) => serde_json::json!({ "count": count }).to_string(),
+        ("json", Some(candidates)) => {
+            serde_json::json!({ "count": count, "candidates": candidates }).to_string()
+        }
+        (_, None) => count.to_string(),
+        (_, Some(candidates)) => format!("{}/{}", count, candidates),
+    }
+}
+
+/// Render inflection groups as `stem: form, form` lines, or a JSON array.
+fn format_groups(groups: &[InflectionGroup], format: &str) -> String {
+    if format == "json" {
+        return serde_json::to_string_pretty(groups).unwrap_or_default();
+    }
+    groups
+        .iter()
+        .map(|g| format!("{}: {}", g.stem, g.forms.join(", ")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Widest bar drawn by [`format_histogram`]; longer bars are scaled down.
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// Render a length histogram as an ASCII bar chart, or a length-to-count
+/// object for JSON.
+fn format_histogram(histogram: &BTreeMap<usize, usize>, format: &str) -> String {
+    if format == "json" {
+        return serde_json::to_string(histogram).unwrap_or_default();
+    }
+    let peak = histogram.values().copied().max().unwrap_or(0);
+    let label_width = histogram
+        .keys()
+        .last()
+        .map_or(1, |len| len.to_string().len());
+    histogram
+        .iter()
+        .map(|(length, &count)| {
+            let bar = (count * HISTOGRAM_WIDTH).div_ceil(peak.max(HISTOGRAM_WIDTH));
+            format!(
+                "{:>width$} | {} {}",
+                length,
+                "#".repeat(bar),
+                count,
+                width = label_width
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render bigram counts NYT-style, one line per first letter
+/// (`AP-3 AR-1`), or a bigram-to-count object for JSON.
+fn format_hints(counts: &BTreeMap<String, usize>, format: &str) -> String {
+    if format == "json" {
+        return serde_json::to_string(counts).unwrap_or_default();
+    }
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = None;
+    for (bigram, count) in counts {
+        let first = bigram.chars().next();
+        let cell = format!("{}-{}", bigram.to_uppercase(), count);
+        match lines.last_mut() {
+            Some(line) if first == current => {
+                line.push(' ');
+                line.push_str(&cell);
+            }
+            _ => lines.push(cell),
+        }
+        current = first;
+    }
+    lines.join("\n")
+}
+
+/// Render the rarest word; JSON is `{"word": ...}`, with `null` when there
+/// are no results.
+fn format_rarest(word: Option<String>, format: &str) -> String {
+    if format == "json" {
+        return serde_json::json!({ "word": word }).to_string();
+    }
+    word.unwrap_or_default()
+}
+
+/// Quote a CSV field per RFC 4180 when it contains a comma, quote, or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// TSV has no quoting, so tabs and line breaks inside a field become spaces.
+fn tsv_field(field: &str) -> String {
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+/// Render rows (header first) as CSV or TSV.
+fn delimited(rows: Vec<Vec<&str>>, format: &str) -> String {
+    let (escape, separator): (fn(&str) -> String, &str) = match format {
+        "tsv" => (tsv_field, "\t"),
+        _ => (csv_field, ","),
+    };
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .map(|f| escape(f))
+                .collect::<Vec<_>>()
+                .join(separator)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Move pangrams to the front, keeping each group's order. Returns how many
+/// pangrams now lead the list.
+fn pangrams_first(words: &mut [String], is_pangram: impl Fn(&str) -> bool) -> usize {
+    words.sort_by_key(|w| !is_pangram(w));
+    words.iter().take_while(|w| is_pangram(w)).count()
+}
+
+/// Join markdown blocks; when `marked` leading blocks are pangrams, split
+/// them from the rest under headings.
+fn markdown_sections(blocks: Vec<String>, marked: usize) -> String {
+    if marked == 0 {
+        return blocks.join("\n\n");
+    }
+    let mut sections = vec![format!("## Pangrams\n\n{}", blocks[..marked].join("\n\n"))];
+    if blocks.len() > marked {
+        sections.push(format!("## Words\n\n{}", blocks[marked..].join("\n\n")));
+    }
+    sections.join("\n\n")
+}
+
+/// Prefix for a plain-format line: `*` marks the `marked` leading pangrams.
+fn plain_marker(index: usize, marked: usize) -> &'static str {
+    if index < marked {
+        "*"
+    } else {
+        ""
+    }
+}
+
+/// Render solve results: JSON is the shared [`SolveResult`] object, other
+/// formats list the words as [`format_unvalidated`] does.
+/// Bonus words follow under their own heading in plain and markdown output;
+/// CSV and TSV gain a `bucket` column (`main` or `bonus`) instead.
+fn format_result(result: &SolveResult, format: &str, marked: usize) -> String {
+    if result.bonus.is_empty() || format == "json" {
+        return match format {
+            "json" => serde_json::to_string_pretty(result).unwrap(),
+            _ => format_unvalidated(&result.words, format, marked),
+        };
+    }
+    if matches!(format, "csv" | "tsv") {
+        let rows = std::iter::once(vec!["word", "bucket"])
+            .chain(result.words.iter().map(|w| vec![w.as_str(), "main"]))
+            .chain(result.bonus.iter().map(|w| vec![w.as_str(), "bonus"]))
+            .collect();
+        return delimited(rows, format);
+    }
+    let words = format_unvalidated(&result.words, format, marked);
+    let bonus = format_unvalidated(&result.bonus, format, 0);
+    match format {
+        "markdown" => format!("{}\n\n## Bonus\n\n{}", words, bonus),
+        _ => format!("{}\n\nBonus:\n{}", words, bonus),
+    }
+}
+
+fn format_unvalidated(words: &[String], format: &str, marked: usize) -> String {
     match format {
         "json" => serde_json::to_string_pretty(words).unwrap(),
-        "markdown" => words
+        "markdown" => {
+            markdown_sections(words.iter().map(|w| format!("**{}**", w)).collect(), marked)
+        }
+        "csv" | "tsv" => delimited(
+            std::iter::once(vec!["word"])
+                .chain(words.iter().map(|w| vec![w.as_str()]))
+                .collect(),
+            format,
+        ),
+        _ => words
             .iter()
-            .map(|w| format!("**{}**", w))
+            .enumerate()
+            .map(|(i, w)| format!("{}{}", plain_marker(i, marked), w))
             .collect::<Vec<_>>()
-            .join("\n\n"),
-        _ => words.join("\n"),
+            .join("\n"),
     }
 }
 
 #[cfg(feature = "validator")]
-fn format_validated(entries: &[sbs::WordEntry], format: &str) -> String {
+fn format_validated(entries: &[sbs::WordEntry], format: &str, marked: usize) -> String {
     match format {
         "json" => serde_json::to_string_pretty(entries).unwrap(),
-        "markdown" => entries
-            .iter()
-            .map(|e| format!("**{}**\n{}", e.word, e.definition))
-            .collect::<Vec<_>>()
-            .join("\n\n"),
+        "markdown" => markdown_sections(
+            entries
+                .iter()
+                .map(|e| {
+                    // Link the word to its source when the validator gives one
+                    let word = if e.url.is_empty() {
+                        e.word.clone()
+                    } else {
+                        format!("[{}]({})", e.word, e.url)
+                    };
+                    match &e.part_of_speech {
+                        Some(pos) => format!("**{}** ({})\n{}", word, pos, e.definition),
+                        None => format!("**{}**\n{}", word, e.definition),
+                    }
+                })
+                .collect(),
+            marked,
+        ),
+        "csv" | "tsv" => delimited(
+            std::iter::once(vec!["word", "definition", "url"])
+                .chain(
+                    entries
+                        .iter()
+                        .map(|e| vec![e.word.as_str(), e.definition.as_str(), e.url.as_str()]),
+                )
+                .collect(),
+            format,
+        ),
         _ => entries
             .iter()
-            .map(|e| format!("{}\t{}", e.word, e.definition))
+            .enumerate()
+            .map(|(i, e)| {
+                format!(
+                    "{}{}\t{}\t{}",
+                    plain_marker(i, marked),
+                    e.word,
+                    e.definition,
+                    e.url
+                )
+            })
             .collect::<Vec<_>>()
             .join("\n"),
     }
@@ -260,25 +979,108 @@ fn write_output(content: &str, out_path: Option<&str>) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_dictionary_flag_wins_even_with_default_path() {
+        let mut config = Config::new().with_dictionary("words.txt");
+        let args = Args::try_parse_from(["sbs", "--dictionary", "data/dictionary.txt"]).unwrap();
+        config.merge(Config::new());
+        apply_dictionary_flag(&mut config, args.dictionary);
+        assert_eq!(config.dictionary, PathBuf::from("data/dictionary.txt"));
+
+        let mut config = Config::new().with_dictionary("words.txt");
+        apply_dictionary_flag(&mut config, None);
+        assert_eq!(config.dictionary, PathBuf::from("words.txt"));
+    }
+
+    #[test]
+    fn test_dictionary_flag_replaces_dictionaries_list() {
+        let mut config = Config::new();
+        config.dictionaries = Some(vec![PathBuf::from("base.txt"), PathBuf::from("names.txt")]);
+        let args = Args::try_parse_from(["sbs", "--dictionary", "mine.txt"]).unwrap();
+        apply_dictionary_flag(&mut config, args.dictionary);
+        assert_eq!(config.dictionaries, None);
+        assert_eq!(config.dictionary, PathBuf::from("mine.txt"));
+
+        let mut config = Config::new();
+        config.dictionaries = Some(vec![PathBuf::from("base.txt")]);
+        apply_dictionary_flag(&mut config, None);
+        assert!(config.dictionaries.is_some(), "kept without the flag");
+    }
+
+    #[test]
+    fn test_min_length_flag_and_alias() {
+        for flag in ["--minimal-word-length", "--min-length"] {
+            let args = Args::try_parse_from(["sbs", "--letters", "abc", flag, "3"]).unwrap();
+            assert_eq!(args.minimal_word_length, Some(3), "{}", flag);
+        }
+        let args = Args::try_parse_from(["sbs", "--max-length", "6"]).unwrap();
+        assert_eq!(args.maximal_word_length, Some(6));
+    }
+
     #[test]
     fn test_format_unvalidated_plain() {
         let words = vec!["apple".to_string(), "bat".to_string()];
-        assert_eq!(format_unvalidated(&words, "plain"), "apple\nbat");
+        assert_eq!(format_unvalidated(&words, "plain", 0), "apple\nbat");
     }
 
     #[test]
     fn test_format_unvalidated_json() {
         let words = vec!["apple".to_string(), "bat".to_string()];
-        let output = format_unvalidated(&words, "json");
+        let output = format_unvalidated(&words, "json", 0);
         let parsed: Vec<String> = serde_json::from_str(&output).unwrap();
         assert_eq!(parsed, vec!["apple", "bat"]);
     }
 
+    #[test]
+    fn test_format_result_bonus_section() {
+        let result = SolveResult {
+            words: vec!["apple".to_string()],
+            pangrams: Vec::new(),
+            count: 1,
+            bonus: vec!["ape".to_string()],
+        };
+        assert_eq!(format_result(&result, "plain", 0), "apple\n\nBonus:\nape");
+        assert_eq!(
+            format_result(&result, "markdown", 0),
+            "**apple**\n\n## Bonus\n\n**ape**"
+        );
+        let parsed: serde_json::Value =
+            serde_json::from_str(&format_result(&result, "json", 0)).unwrap();
+        assert_eq!(parsed["bonus"], serde_json::json!(["ape"]));
+        assert_eq!(parsed["count"], 1);
+        assert_eq!(
+            format_result(&result, "csv", 0),
+            "word,bucket\napple,main\nape,bonus"
+        );
+        assert_eq!(
+            format_result(&result, "tsv", 0),
+            "word\tbucket\napple\tmain\nape\tbonus"
+        );
+    }
+
+    #[test]
+    fn test_format_result_json_matches_shared_shape() {
+        let result = SolveResult {
+            words: vec!["apple".to_string(), "plate".to_string()],
+            pangrams: vec!["plate".to_string()],
+            count: 2,
+            bonus: Vec::new(),
+        };
+        let parsed: serde_json::Value =
+            serde_json::from_str(&format_result(&result, "json", 0)).unwrap();
+        assert_eq!(parsed, serde_json::to_value(&result).unwrap());
+        assert_eq!(
+            parsed,
+            serde_json::json!({"words": ["apple", "plate"], "pangrams": ["plate"], "count": 2})
+        );
+        assert_eq!(format_result(&result, "plain", 0), "apple\nplate");
+    }
+
     #[test]
     fn test_format_unvalidated_markdown() {
         let words = vec!["apple".to_string(), "bat".to_string()];
         assert_eq!(
-            format_unvalidated(&words, "markdown"),
+            format_unvalidated(&words, "markdown", 0),
             "**apple**\n\n**bat**"
         );
     }
@@ -290,8 +1092,13 @@ mod tests {
             word: "apple".to_string(),
             definition: "A fruit".to_string(),
             url: "https://example.com/apple".to_string(),
+            definitions: vec!["A fruit".to_string()],
+            part_of_speech: None,
         }];
-        assert_eq!(format_validated(&entries, "plain"), "apple\tA fruit");
+        assert_eq!(
+            format_validated(&entries, "plain", 0),
+            "apple\tA fruit\thttps://example.com/apple"
+        );
     }
 
     #[cfg(feature = "validator")]
@@ -301,11 +1108,14 @@ mod tests {
             word: "apple".to_string(),
             definition: "A fruit".to_string(),
             url: "https://example.com/apple".to_string(),
+            definitions: vec!["A fruit".to_string()],
+            part_of_speech: None,
         }];
-        let output = format_validated(&entries, "json");
+        let output = format_validated(&entries, "json", 0);
         let parsed: Vec<serde_json::Value> = serde_json::from_str(&output).unwrap();
         assert_eq!(parsed[0]["word"], "apple");
         assert_eq!(parsed[0]["definition"], "A fruit");
+        assert_eq!(parsed[0]["url"], "https://example.com/apple");
     }
 
     #[cfg(feature = "validator")]
@@ -315,7 +1125,273 @@ mod tests {
             word: "apple".to_string(),
             definition: "A fruit".to_string(),
             url: "https://example.com/apple".to_string(),
+            definitions: vec!["A fruit".to_string()],
+            part_of_speech: None,
+        }];
+        assert_eq!(
+            format_validated(&entries, "markdown", 0),
+            "**[apple](https://example.com/apple)**\nA fruit"
+        );
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn test_format_validated_without_url() {
+        let entries = vec![sbs::WordEntry {
+            word: "apple".to_string(),
+            definition: "A fruit".to_string(),
+            url: String::new(),
+            definitions: vec!["A fruit".to_string()],
+            part_of_speech: None,
+        }];
+        assert_eq!(
+            format_validated(&entries, "plain", 0),
+            "apple\tA fruit\t",
+            "the URL column is kept for a stable layout"
+        );
+        assert_eq!(
+            format_validated(&entries, "markdown", 0),
+            "**apple**\nA fruit"
+        );
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn test_format_validated_markdown_part_of_speech() {
+        let entries = vec![sbs::WordEntry {
+            word: "apple".to_string(),
+            definition: "A fruit".to_string(),
+            url: "https://example.com/apple".to_string(),
+            definitions: vec!["A fruit".to_string()],
+            part_of_speech: Some("noun".to_string()),
         }];
-        assert_eq!(format_validated(&entries, "markdown"), "**apple**\nA fruit");
+        assert_eq!(
+            format_validated(&entries, "markdown", 0),
+            "**[apple](https://example.com/apple)** (noun)\nA fruit"
+        );
+    }
+
+    fn is_test_pangram(word: &str) -> bool {
+        ["pleat", "plate"].contains(&word)
+    }
+
+    #[test]
+    fn test_pangrams_first_orders_and_counts() {
+        let mut words: Vec<String> = ["leap", "pale", "plate", "peal", "pleat"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let marked = pangrams_first(&mut words, is_test_pangram);
+        assert_eq!(marked, 2);
+        assert_eq!(words, vec!["plate", "pleat", "leap", "pale", "peal"]);
+    }
+
+    #[test]
+    fn test_format_unvalidated_plain_marks_pangrams() {
+        let words: Vec<String> = ["plate", "pleat", "leap"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        assert_eq!(
+            format_unvalidated(&words, "plain", 2),
+            "*plate\n*pleat\nleap"
+        );
+    }
+
+    #[test]
+    fn test_format_unvalidated_markdown_pangram_heading() {
+        let words: Vec<String> = ["plate", "leap"].iter().map(|w| w.to_string()).collect();
+        assert_eq!(
+            format_unvalidated(&words, "markdown", 1),
+            "## Pangrams\n\n**plate**\n\n## Words\n\n**leap**"
+        );
+        assert_eq!(
+            format_unvalidated(&words[..1], "markdown", 1),
+            "## Pangrams\n\n**plate**"
+        );
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(12, None, "plain"), "12");
+        assert_eq!(format_count(12, None, "json"), r#"{"count":12}"#);
+        assert_eq!(format_count(3, Some(12), "plain"), "3/12");
+        let parsed: serde_json::Value =
+            serde_json::from_str(&format_count(3, Some(12), "json")).unwrap();
+        assert_eq!(parsed["count"], 3);
+        assert_eq!(parsed["candidates"], 12);
+    }
+
+    #[test]
+    fn test_format_groups() {
+        let groups =
+            sbs::group_inflections(&["run".to_string(), "runs".to_string(), "bus".to_string()]);
+        assert_eq!(format_groups(&groups, "plain"), "bus: bus\nrun: run, runs");
+        let parsed: serde_json::Value =
+            serde_json::from_str(&format_groups(&groups, "json")).unwrap();
+        assert_eq!(parsed[1]["forms"][1], "runs");
+    }
+
+    #[test]
+    fn test_format_histogram() {
+        let histogram = BTreeMap::from([(4, 3), (5, 1), (12, 2)]);
+        assert_eq!(
+            format_histogram(&histogram, "plain"),
+            " 4 | ### 3\n 5 | # 1\n12 | ## 2"
+        );
+        assert_eq!(
+            format_histogram(&histogram, "json"),
+            r#"{"4":3,"5":1,"12":2}"#
+        );
+
+        let scaled = format_histogram(&BTreeMap::from([(4, 80), (5, 1)]), "plain");
+        assert_eq!(scaled, format!("4 | {} 80\n5 | # 1", "#".repeat(40)));
+    }
+
+    #[test]
+    fn test_format_hints() {
+        let counts: BTreeMap<String, usize> = [("ap", 3), ("ar", 1), ("ba", 2)]
+            .into_iter()
+            .map(|(bigram, count)| (bigram.to_string(), count))
+            .collect();
+        assert_eq!(format_hints(&counts, "plain"), "AP-3 AR-1\nBA-2");
+        assert_eq!(format_hints(&counts, "json"), r#"{"ap":3,"ar":1,"ba":2}"#);
+        assert_eq!(format_hints(&BTreeMap::new(), "plain"), "");
+    }
+
+    #[test]
+    fn test_format_rarest() {
+        assert_eq!(format_rarest(Some("fazed".to_string()), "plain"), "fazed");
+        assert_eq!(
+            format_rarest(Some("fazed".to_string()), "json"),
+            r#"{"word":"fazed"}"#
+        );
+        assert_eq!(format_rarest(None, "json"), r#"{"word":null}"#);
+        assert_eq!(format_rarest(None, "plain"), "");
+    }
+
+    #[test]
+    fn test_format_unvalidated_csv() {
+        let words = vec!["apple".to_string(), "bat".to_string()];
+        assert_eq!(format_unvalidated(&words, "csv", 0), "word\napple\nbat");
+        assert_eq!(format_unvalidated(&words, "tsv", 0), "word\napple\nbat");
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn test_format_validated_csv_escapes_fields() {
+        let entries = vec![sbs::WordEntry {
+            word: "apple".to_string(),
+            definition: "A \"pome\" fruit, round".to_string(),
+            url: "https://example.com/apple".to_string(),
+            definitions: vec![],
+            part_of_speech: None,
+        }];
+        assert_eq!(
+            format_validated(&entries, "csv", 0),
+            "word,definition,url\napple,\"A \"\"pome\"\" fruit, round\",https://example.com/apple"
+        );
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn test_format_validated_tsv() {
+        let entries = vec![sbs::WordEntry {
+            word: "apple".to_string(),
+            definition: "A fruit,\tround".to_string(),
+            url: "https://example.com/apple".to_string(),
+            definitions: vec![],
+            part_of_speech: None,
+        }];
+        assert_eq!(
+            format_validated(&entries, "tsv", 0),
+            "word\tdefinition\turl\napple\tA fruit, round\thttps://example.com/apple"
+        );
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_print_completions_bash() {
+        let mut out = Vec::new();
+        print_completions(Shell::Bash, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("_sbs()"));
+        assert!(script.contains("--available-letters"));
+        assert!(script.contains("--format"));
+    }
+
+    #[test]
+    fn test_read_word_list() {
+        let words = read_word_list(std::io::Cursor::new("# brands\nAcme\n\n  zorb  \n")).unwrap();
+        assert_eq!(words, ["Acme", "zorb"]);
+    }
+
+    #[test]
+    fn test_read_puzzle() {
+        let parse = |input: &str| read_puzzle(std::io::Cursor::new(input));
+
+        assert_eq!(
+            parse("abcdefg a\n"),
+            Ok(("abcdefg".to_string(), Some("a".to_string())))
+        );
+        assert_eq!(parse("  abcdefg  \n"), Ok(("abcdefg".to_string(), None)));
+        assert_eq!(parse("abc a\nxyz x\n").unwrap().0, "abc", "first line only");
+        assert!(parse("").is_err());
+        assert!(parse("   \n").is_err());
+    }
+
+    #[test]
+    fn test_run_interactive_session() {
+        let dictionary = Dictionary::from_words(&["apple", "pale", "leap", "plea", "pleat"]);
+        let input = "letters aelp\npresent a\nsolve\nmin 5\nsolve\nbogus\nquit\nsolve\n";
+        let mut output = Vec::new();
+
+        run_interactive(
+            std::io::Cursor::new(input),
+            &mut output,
+            &dictionary,
+            Config::new(),
+            "plain",
+        )
+        .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let mut lines = output.lines();
+        assert_eq!(
+            lines.by_ref().take(4).collect::<Vec<_>>(),
+            vec!["apple", "leap", "pale", "plea"]
+        );
+        assert_eq!(
+            lines.next(),
+            Some("apple"),
+            "min 5 applies to the next solve"
+        );
+        assert!(lines.next().unwrap().starts_with("Unknown command 'bogus'"));
+        assert_eq!(lines.next(), None, "nothing runs after quit");
+    }
+
+    #[test]
+    fn test_run_interactive_ends_at_eof() {
+        let dictionary = Dictionary::from_words(&["apple"]);
+        let mut output = Vec::new();
+        run_interactive(
+            std::io::Cursor::new("solve\n"),
+            &mut output,
+            &dictionary,
+            Config::new(),
+            "plain",
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Error: letters are required.\n"
+        );
     }
 }
@@ -1,33 +1,884 @@
 //! The algorithmic core: Trie-based solver.
 
-use crate::config::Config;
-use crate::dictionary::{Dictionary, TrieNode};
+use crate::config::{Config, SortOrder};
+use crate::dictionary::{Dictionary, TrieNode, CONNECTORS};
 use crate::error::SbsError;
-use std::collections::{HashMap, HashSet};
+use crate::inflections::{group_inflections, InflectionGroup};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Trie nodes visited between clock reads when a solve has a deadline.
+const DEADLINE_CHECK_INTERVAL: usize = 1024;
+
+/// Puzzles remembered by [`CachingSolver::new`] before the cache is cleared.
+pub const DEFAULT_SOLVE_CACHE_CAPACITY: usize = 256;
 
-pub struct Solver {
-    config: Config,
+#[cfg(test)]
+thread_local! {
+    /// Trie searches started on this thread, so tests can tell cache hits from misses.
+    static TRIE_SEARCHES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
 }
 
-/// Context struct to reduce argument count in recursion
-struct SearchContext<'a> {
-    allowed: &'a HashSet<char>,
-    anywhere: &'a HashSet<char>,
-    required: &'a HashSet<char>,
+/// Solves puzzles described by a [`Config`], which it owns or borrows.
+pub struct Solver<'c> {
+    config: Cow<'c, Config>,
+}
+
+/// A solution annotated with puzzle-specific metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolvedWord {
+    pub word: String,
+    /// True when the word uses every available letter at least once.
+    pub is_pangram: bool,
+    /// True when the word uses every available letter exactly once.
+    #[serde(default)]
+    pub is_perfect_pangram: bool,
+    pub length: usize,
+    /// Spelling Bee score: see [`Solver::score_word`].
+    pub score: u32,
+    /// Available letters the word uses.
+    pub used_letters: BTreeSet<char>,
+    /// Available letters the word leaves out; empty for pangrams.
+    pub unused_letters: BTreeSet<char>,
+}
+
+/// Annotated solutions together with the puzzle's aggregate score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolveSummary {
+    pub words: Vec<SolvedWord>,
+    pub total_score: u32,
+}
+
+/// Sorted solutions with their pangrams: the JSON shape shared by the CLI,
+/// the server's `/solve`, and the FFI's `sbs_solve`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolveResult {
+    pub words: Vec<String>,
+    /// The pangrams among `words`, in the same order.
+    pub pangrams: Vec<String>,
+    pub count: usize,
+    /// Words shorter than the minimum length but at least `bonus-min-length`
+    /// long; never part of `words` or `count`. Omitted from JSON when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bonus: Vec<String>,
+}
+
+/// The six least frequent letters in English text, used to rank word rarity
+/// when the word list carries no frequencies; see [`Solver::rarest_word`].
+const RARE_LETTERS: &str = "jqxzkv";
+
+/// Bonus points awarded to pangrams on top of their length score.
+const PANGRAM_BONUS: u32 = 7;
+
+/// Word constraints resolved from a `Config`, shared by every search strategy.
+struct Constraints {
+    allowed: HashSet<char>,
+    anywhere: HashSet<char>,
+    required: HashSet<char>,
+    /// How many distinct `required` letters a word must contain.
+    required_min: usize,
     required_start: Option<char>,
     case_sensitive: bool,
     min_len: usize,
     max_len: usize,
     max_repeats: Option<usize>,
+    min_distinct: usize,
+    pattern: Option<Regex>,
+    prefix: Vec<char>,
+    suffix: String,
+    infix: String,
+    /// Words never accepted, lowercase.
+    blocklist: HashSet<String>,
+    /// When set, the only words accepted, lowercase.
+    allowlist: Option<HashSet<String>>,
+    preserve_case: bool,
+    allow_punctuation: bool,
+}
+
+impl Constraints {
+    /// Whether `ch` may be appended to a word of length `depth`.
+    fn can_extend(&self, depth: usize, ch: char, char_counts: &HashMap<char, usize>) -> bool {
+        // Prune subtrees that diverge from the required prefix
+        if let Some(&expected) = self.prefix.get(depth) {
+            if ch != expected {
+                return false;
+            }
+        }
+
+        // Connectors join letters freely and are not available letters
+        if self.allow_punctuation && CONNECTORS.contains(&ch) {
+            return depth > 0;
+        }
+
+        // In case-sensitive mode, start-only chars can only appear at depth 0
+        let char_allowed = if self.case_sensitive && depth > 0 {
+            self.anywhere.contains(&ch)
+        } else {
+            self.allowed.contains(&ch)
+        };
+        if !char_allowed {
+            return false;
+        }
+
+        // Check repetition limit
+        let count = *char_counts.get(&ch).unwrap_or(&0);
+        match self.max_repeats {
+            Some(limit) => count < limit,
+            None => true,
+        }
+    }
+
+    /// Whether a complete dictionary word satisfies every acceptance check.
+    fn accepts(&self, word: &str, char_counts: &HashMap<char, usize>) -> bool {
+        if word.len() < self.min_len || word.len() > self.max_len {
+            return false;
+        }
+        let required_present = self
+            .required
+            .iter()
+            .filter(|req| *char_counts.get(req).unwrap_or(&0) > 0)
+            .count();
+        if required_present < self.required_min {
+            return false;
+        }
+        if !word.contains(&self.infix) {
+            return false;
+        }
+        // If case-sensitive and required_start is set, first char must match
+        if let Some(start_char) = self.required_start {
+            if !word.starts_with(start_char) {
+                return false;
+            }
+        }
+        // Counts are keyed by lowercase letter, so start-only and anywhere
+        // variants of the same letter count once.
+        if self.min_distinct > 0
+            && char_counts
+                .iter()
+                .filter(|(ch, &count)| count > 0 && !CONNECTORS.contains(ch))
+                .count()
+                < self.min_distinct
+        {
+            return false;
+        }
+        if word.chars().count() < self.prefix.len() || !word.ends_with(&self.suffix) {
+            return false;
+        }
+        if let Some(re) = &self.pattern {
+            if !re.is_match(word) {
+                return false;
+            }
+        }
+        if self.blocklist.contains(word) {
+            return false;
+        }
+        if let Some(allowlist) = &self.allowlist {
+            if !allowlist.contains(word) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The string to report for an accepted word ending at `node`.
+    fn emit(&self, node: &TrieNode, word: &str) -> String {
+        match &node.original {
+            Some(original) if self.preserve_case => original.clone(),
+            _ => word.to_string(),
+        }
+    }
+}
+
+/// Search for words drawn from a bounded letter multiset.
+struct PoolSearch {
+    min_len: usize,
+    /// Only accept words that use up the whole pool.
+    exhaust: bool,
+    preserve_case: bool,
+}
+
+impl PoolSearch {
+    fn run(&self, dictionary: &Dictionary, letters: &str) -> Result<HashSet<String>, SbsError> {
+        let mut pool: HashMap<char, usize> = HashMap::new();
+        for ch in letters
+            .to_lowercase()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+        {
+            *pool.entry(ch).or_insert(0) += 1;
+        }
+        if pool.is_empty() {
+            return Err(SbsError::ConfigError("No letters provided".to_string()));
+        }
+
+        let left = pool.values().sum();
+        let mut results = HashSet::new();
+        self.walk(
+            &dictionary.root,
+            &mut String::new(),
+            left,
+            &mut pool,
+            &mut results,
+        );
+        Ok(results)
+    }
+
+    fn walk(
+        &self,
+        node: &TrieNode,
+        word: &mut String,
+        left: usize,
+        pool: &mut HashMap<char, usize>,
+        results: &mut HashSet<String>,
+    ) {
+        let complete = if self.exhaust {
+            left == 0
+        } else {
+            word.chars().count() >= self.min_len
+        };
+        if node.is_end_of_word && complete {
+            results.insert(match &node.original {
+                Some(original) if self.preserve_case => original.clone(),
+                _ => word.clone(),
+            });
+        }
+
+        for (ch, child) in &node.children {
+            if let Some(count) = pool.get_mut(ch).filter(|count| **count > 0) {
+                *count -= 1;
+                word.push(*ch);
+                self.walk(child, word, left - 1, pool, results);
+                word.pop();
+                *pool.get_mut(ch).unwrap() += 1;
+            }
+        }
+    }
+}
+
+/// Trie walk collecting words within a Levenshtein distance of a target,
+/// carrying one dynamic-programming row per trie level.
+struct EditSearch {
+    target: Vec<char>,
+    max_distance: usize,
+    preserve_case: bool,
+}
+
+impl EditSearch {
+    fn run(&self, dictionary: &Dictionary) -> Vec<(usize, String)> {
+        let first_row: Vec<usize> = (0..=self.target.len()).collect();
+        let mut results = Vec::new();
+        self.walk(
+            &dictionary.root,
+            &mut String::new(),
+            &first_row,
+            &mut results,
+        );
+        results
+    }
+
+    /// `row[i]` is the distance between `word` and the first `i` target letters.
+    fn walk(
+        &self,
+        node: &TrieNode,
+        word: &mut String,
+        row: &[usize],
+        results: &mut Vec<(usize, String)>,
+    ) {
+        for (ch, child) in &node.children {
+            let mut next = Vec::with_capacity(row.len());
+            next.push(row[0] + 1);
+            for (i, target_ch) in self.target.iter().enumerate() {
+                let substitute = row[i] + usize::from(target_ch != ch);
+                next.push(substitute.min(row[i + 1] + 1).min(next[i] + 1));
+            }
+
+            word.push(*ch);
+            let distance = next[self.target.len()];
+            if child.is_end_of_word && distance <= self.max_distance {
+                let found = match &child.original {
+                    Some(original) if self.preserve_case => original.clone(),
+                    _ => word.clone(),
+                };
+                results.push((distance, found));
+            }
+            // No extension can get closer than the row's best cell.
+            if next
+                .iter()
+                .min()
+                .is_some_and(|&best| best <= self.max_distance)
+            {
+                self.walk(child, word, &next, results);
+            }
+            word.pop();
+        }
+    }
+}
+
+/// Context struct to reduce argument count in recursion
+struct SearchContext<'a> {
+    constraints: &'a Constraints,
     results: &'a mut HashSet<String>,
+    /// Called with the running result count after each top-level subtree.
+    on_progress: &'a dyn Fn(usize),
+    /// Abandon the search once this instant passes.
+    deadline: Option<Instant>,
+    /// Nodes entered since the search started, to pace deadline checks.
+    nodes: usize,
+    timed_out: bool,
+    /// Nodes entered by `find_words`, to check pruning in tests.
+    #[cfg(test)]
+    visited: usize,
 }
 
-impl Solver {
+/// Letter sets derived from the config's `letters` and `present`.
+struct LetterSets {
+    allowed: HashSet<char>,
+    anywhere: HashSet<char>,
+    required: HashSet<char>,
+    required_start: Option<char>,
+}
+
+impl<'c> Solver<'c> {
     pub fn new(config: Config) -> Self {
-        Self { config }
+        Self {
+            config: Cow::Owned(config),
+        }
     }
 
+    /// Build a solver that borrows `config`, so callers can keep using it
+    /// without cloning.
+    pub fn from_config_ref(config: &'c Config) -> Self {
+        Self {
+            config: Cow::Borrowed(config),
+        }
+    }
+
+    /// Solve the puzzle. The set has no stable iteration order; use
+    /// [`Solver::solve_sorted`] for deterministic output.
     pub fn solve(&self, dictionary: &Dictionary) -> Result<HashSet<String>, SbsError> {
+        self.solve_with_progress(dictionary, &|_| {})
+    }
+
+    /// Solve, calling `on_progress` with the number of words found so far each
+    /// time the search finishes a top-level subtree (one per starting letter),
+    /// so callers get a few dozen updates however large the dictionary is.
+    pub fn solve_with_progress(
+        &self,
+        dictionary: &Dictionary,
+        on_progress: &dyn Fn(usize),
+    ) -> Result<HashSet<String>, SbsError> {
+        self.search(dictionary, on_progress, None)
+    }
+
+    /// Solve, giving up with [`SbsError::Timeout`] once `deadline` passes.
+    ///
+    /// The clock is read once per [`DEADLINE_CHECK_INTERVAL`] trie nodes, so
+    /// checking adds little overhead and the search stops soon after the deadline.
+    pub fn solve_with_deadline(
+        &self,
+        dictionary: &Dictionary,
+        deadline: Instant,
+    ) -> Result<HashSet<String>, SbsError> {
+        self.search(dictionary, &|_| {}, Some(deadline))
+    }
+
+    /// Like [`Solver::solve_sorted`], bounded by `deadline` as in
+    /// [`Solver::solve_with_deadline`].
+    pub fn solve_sorted_with_deadline(
+        &self,
+        dictionary: &Dictionary,
+        deadline: Instant,
+    ) -> Result<Vec<String>, SbsError> {
+        let mut words: Vec<String> = self
+            .solve_with_deadline(dictionary, deadline)?
+            .into_iter()
+            .collect();
+        self.sort_by_order(&mut words, Some(dictionary));
+        Ok(words)
+    }
+
+    fn search(
+        &self,
+        dictionary: &Dictionary,
+        on_progress: &dyn Fn(usize),
+        deadline: Option<Instant>,
+    ) -> Result<HashSet<String>, SbsError> {
+        let constraints = self.constraints()?;
+        let mut results = HashSet::new();
+        #[cfg(test)]
+        TRIE_SEARCHES.with(|searches| searches.set(searches.get() + 1));
+
+        let mut ctx = SearchContext {
+            constraints: &constraints,
+            results: &mut results,
+            on_progress,
+            deadline,
+            nodes: 0,
+            timed_out: false,
+            #[cfg(test)]
+            visited: 0,
+        };
+
+        let mut char_counts = HashMap::new();
+
+        Self::find_words(&dictionary.root, String::new(), &mut char_counts, &mut ctx);
+        if ctx.timed_out {
+            return Err(SbsError::Timeout(format!(
+                "solve exceeded its deadline after visiting {} nodes",
+                ctx.nodes
+            )));
+        }
+
+        if let Some(cap) = self.config.max_results {
+            results = Self::cap_results(results, cap);
+        }
+
+        Ok(results)
+    }
+
+    /// Solve several puzzles against one shared dictionary, in order.
+    ///
+    /// Each config gets its own result, so one invalid puzzle does not fail
+    /// the others.
+    pub fn solve_batch(
+        dictionary: &Dictionary,
+        configs: &[Config],
+    ) -> Vec<Result<HashSet<String>, SbsError>> {
+        configs
+            .iter()
+            .map(|config| Solver::from_config_ref(config).solve(dictionary))
+            .collect()
+    }
+
+    /// Solve and return the words in the config's [`SortOrder`]; ties break
+    /// alphabetically, so the order is the same on every run.
+    pub fn solve_sorted(&self, dictionary: &Dictionary) -> Result<Vec<String>, SbsError> {
+        let mut words: Vec<String> = self.solve(dictionary)?.into_iter().collect();
+        self.sort_by_order(&mut words, Some(dictionary));
+        Ok(words)
+    }
+
+    /// Solve into a [`SolveResult`], with words in the configured order and
+    /// any bonus words (see [`Solver::solve_with_bonus`]) kept apart.
+    pub fn solve_result(&self, dictionary: &Dictionary) -> Result<SolveResult, SbsError> {
+        let (words, bonus) = self.solve_with_bonus(dictionary)?;
+        Ok(SolveResult {
+            bonus,
+            ..self.result_from_words(words)
+        })
+    }
+
+    /// Solve with the length threshold lowered to `bonus-min-length`, then
+    /// split the sorted results into regular words and shorter bonus words.
+    ///
+    /// Without a `bonus-min-length` below the minimal word length, the bonus
+    /// list is empty and this matches [`Solver::solve_sorted`].
+    pub fn solve_with_bonus(
+        &self,
+        dictionary: &Dictionary,
+    ) -> Result<(Vec<String>, Vec<String>), SbsError> {
+        let min_len = self.config.min_length();
+        let Some(bonus_min) = self.config.bonus_min_length.filter(|&b| b < min_len) else {
+            return Ok((self.solve_sorted(dictionary)?, Vec::new()));
+        };
+        let mut lowered = self.config.clone().into_owned();
+        lowered.minimal_word_length = Some(bonus_min);
+        let words = Solver::new(lowered).solve_sorted(dictionary)?;
+        Ok(words
+            .into_iter()
+            .partition(|word| word.chars().count() >= min_len))
+    }
+
+    /// Wrap already solved and ordered `words` in a [`SolveResult`], picking
+    /// out the pangrams under this solver's letters.
+    pub fn result_from_words(&self, words: Vec<String>) -> SolveResult {
+        let pangrams = words
+            .iter()
+            .filter(|word| self.word_is_pangram(word))
+            .cloned()
+            .collect();
+        SolveResult {
+            count: words.len(),
+            words,
+            pangrams,
+            bonus: Vec::new(),
+        }
+    }
+
+    /// Sort words in the config's [`SortOrder`], breaking ties alphabetically.
+    ///
+    /// [`SortOrder::Frequency`] needs the dictionary's weights, so here it
+    /// falls back to alphabetical; use [`Solver::solve_sorted`] instead.
+    pub fn sort_words(&self, words: &mut [String]) {
+        self.sort_by_order(words, None);
+    }
+
+    fn sort_by_order(&self, words: &mut [String], dictionary: Option<&Dictionary>) {
+        let len = |w: &String| w.chars().count();
+        match self.config.sort_order() {
+            SortOrder::Alpha => words.sort(),
+            SortOrder::Length => words.sort_by_cached_key(|w| (len(w), w.clone())),
+            SortOrder::LengthDesc => words.sort_by_cached_key(|w| (Reverse(len(w)), w.clone())),
+            SortOrder::Score => {
+                let allowed = self
+                    .letter_sets()
+                    .map(|sets| sets.allowed)
+                    .unwrap_or_default();
+                words.sort_by_cached_key(|w| {
+                    let score = Self::score(len(w), Self::is_pangram(w, &allowed));
+                    (Reverse(score), w.clone())
+                });
+            }
+            SortOrder::Frequency => words.sort_by_cached_key(|w| {
+                let weight = dictionary.map_or(0, |d| d.weight(w));
+                (Reverse(weight), w.clone())
+            }),
+        }
+    }
+
+    /// Keep the `cap` best results, ranked by length (longest first) then alphabetically.
+    ///
+    /// The trie walk order is arbitrary, so the full result set is ranked
+    /// before truncating rather than stopping the search early.
+    fn cap_results(results: HashSet<String>, cap: usize) -> HashSet<String> {
+        if results.len() <= cap {
+            return results;
+        }
+        let mut ranked: Vec<String> = results.into_iter().collect();
+        ranked.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        ranked.truncate(cap);
+        ranked.into_iter().collect()
+    }
+
+    /// Solve and annotate each word with pangram status, length and score.
+    ///
+    /// Results are sorted alphabetically.
+    pub fn solve_with_metadata(
+        &self,
+        dictionary: &Dictionary,
+    ) -> Result<Vec<SolvedWord>, SbsError> {
+        let allowed = self.letter_sets()?.allowed;
+        let mut words: Vec<String> = self.solve(dictionary)?.into_iter().collect();
+        words.sort();
+
+        Ok(words
+            .into_iter()
+            .map(|word| Self::annotate(word, &allowed))
+            .collect())
+    }
+
+    /// Solve and return the annotated words with their total score.
+    pub fn solve_summary(&self, dictionary: &Dictionary) -> Result<SolveSummary, SbsError> {
+        let words = self.solve_with_metadata(dictionary)?;
+        let total_score = words.iter().map(|w| w.score).sum();
+        Ok(SolveSummary { words, total_score })
+    }
+
+    /// Find words that use exactly the given letters, each occurrence once.
+    ///
+    /// Unlike [`Solver::solve`], this ignores the puzzle config except for
+    /// `preserve-case`: `letters` is a multiset that every result must
+    /// exhaust, so only full-length rearrangements are returned.
+    pub fn anagrams(
+        &self,
+        dictionary: &Dictionary,
+        letters: &str,
+    ) -> Result<HashSet<String>, SbsError> {
+        let pool = PoolSearch {
+            min_len: 0,
+            exhaust: true,
+            preserve_case: self.config.preserve_case.unwrap_or(false),
+        };
+        pool.run(dictionary, letters)
+    }
+
+    /// Find words buildable from the letters of `source`, each letter usable
+    /// up to its count in `source` (e.g. "bee" needs two e's).
+    ///
+    /// Respects `minimal-word-length` and `preserve-case`; other puzzle
+    /// constraints are ignored.
+    pub fn words_from_letters_of(
+        &self,
+        dictionary: &Dictionary,
+        source: &str,
+    ) -> Result<HashSet<String>, SbsError> {
+        let pool = PoolSearch {
+            min_len: self.config.min_length(),
+            exhaust: false,
+            preserve_case: self.config.preserve_case.unwrap_or(false),
+        };
+        pool.run(dictionary, source)
+    }
+
+    /// Find dictionary words within `max_distance` edits (insertions,
+    /// deletions, substitutions) of `target`, closest first and then
+    /// alphabetically. `target` itself is included when it is a word.
+    ///
+    /// Ignores the puzzle config except for `preserve-case`; the trie walk
+    /// abandons any branch that can no longer come within `max_distance`.
+    pub fn nearest_words(
+        &self,
+        dictionary: &Dictionary,
+        target: &str,
+        max_distance: usize,
+    ) -> Vec<String> {
+        let search = EditSearch {
+            target: target.trim().to_lowercase().chars().collect(),
+            max_distance,
+            preserve_case: self.config.preserve_case.unwrap_or(false),
+        };
+        let mut results = search.run(dictionary);
+        results.sort();
+        results.into_iter().map(|(_, word)| word).collect()
+    }
+
+    /// Solve and collapse inflected results under their stems; see
+    /// [`group_inflections`].
+    pub fn solve_grouped(&self, dictionary: &Dictionary) -> Result<Vec<InflectionGroup>, SbsError> {
+        Ok(group_inflections(&self.solve_sorted(dictionary)?))
+    }
+
+    /// Solve and count the results by word length.
+    pub fn length_histogram(
+        &self,
+        dictionary: &Dictionary,
+    ) -> Result<BTreeMap<usize, usize>, SbsError> {
+        Ok(Self::count_lengths(&self.solve(dictionary)?))
+    }
+
+    /// Map each word length (in characters) to the number of `words` of that length.
+    pub fn count_lengths<'a>(
+        words: impl IntoIterator<Item = &'a String>,
+    ) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        for word in words {
+            *histogram.entry(word.chars().count()).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    /// Solve and count the results by their first two letters, like the NYT
+    /// "two-letter list" hint.
+    pub fn two_letter_counts(
+        &self,
+        dictionary: &Dictionary,
+    ) -> Result<BTreeMap<String, usize>, SbsError> {
+        Ok(Self::count_bigrams(&self.solve(dictionary)?))
+    }
+
+    /// Map each leading bigram (lowercased) to the number of `words` starting
+    /// with it. Words shorter than two letters are not counted.
+    pub fn count_bigrams<'a>(
+        words: impl IntoIterator<Item = &'a String>,
+    ) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for word in words {
+            let bigram: String = word.chars().take(2).collect();
+            if bigram.chars().count() == 2 {
+                *counts.entry(bigram.to_lowercase()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Solve and pick the single rarest result, or `None` when nothing matches.
+    ///
+    /// See [`Solver::rarest_of`] for how rarity is judged.
+    pub fn rarest_word(&self, dictionary: &Dictionary) -> Result<Option<String>, SbsError> {
+        Ok(Self::rarest_of(&self.solve(dictionary)?, dictionary))
+    }
+
+    /// Pick the rarest of `words`.
+    ///
+    /// When the word list has frequency weights for any of them, the weighted
+    /// word with the lowest weight wins. Otherwise rarity is a heuristic: the
+    /// longest word, then the one with the most uncommon letters (j, q, x, z,
+    /// k, v). Remaining ties break alphabetically.
+    pub fn rarest_of<'a>(
+        words: impl IntoIterator<Item = &'a String>,
+        dictionary: &Dictionary,
+    ) -> Option<String> {
+        let words: Vec<&String> = words.into_iter().collect();
+        let weighted = words
+            .iter()
+            .map(|w| (dictionary.weight(w), *w))
+            .filter(|(weight, _)| *weight > 0)
+            .min();
+        if let Some((_, word)) = weighted {
+            return Some(word.clone());
+        }
+
+        let rare_letters = |w: &str| {
+            w.to_lowercase()
+                .chars()
+                .filter(|c| RARE_LETTERS.contains(*c))
+                .count()
+        };
+        words
+            .into_iter()
+            .min_by_key(|w| (Reverse(w.chars().count()), Reverse(rare_letters(w)), *w))
+            .cloned()
+    }
+
+    /// Score a word using NYT Spelling Bee rules.
+    ///
+    /// Words of four letters or fewer earn 1 point, longer words earn 1 point
+    /// per letter, and pangrams earn a further 7 points.
+    pub fn score_word(&self, word: &str) -> u32 {
+        Self::score(word.chars().count(), self.word_is_pangram(word))
+    }
+
+    /// Whether `word` uses every available letter. Invalid configs yield `false`.
+    pub fn word_is_pangram(&self, word: &str) -> bool {
+        self.letter_sets()
+            .map(|sets| Self::is_pangram(word, &sets.allowed))
+            .unwrap_or(false)
+    }
+
+    /// Whether `word` uses every available letter exactly once. Invalid
+    /// configs yield `false`.
+    pub fn word_is_perfect_pangram(&self, word: &str) -> bool {
+        self.letter_sets()
+            .map(|sets| Self::is_perfect_pangram(word, &sets.allowed))
+            .unwrap_or(false)
+    }
+
+    fn annotate(word: String, allowed: &HashSet<char>) -> SolvedWord {
+        let is_pangram = Self::is_pangram(&word, allowed);
+        let is_perfect_pangram = is_pangram && Self::is_perfect_pangram(&word, allowed);
+        let length = word.chars().count();
+        let lowered = word.to_lowercase();
+        let (used_letters, unused_letters) = allowed.iter().partition(|ch| lowered.contains(**ch));
+        SolvedWord {
+            word,
+            is_pangram,
+            is_perfect_pangram,
+            length,
+            score: Self::score(length, is_pangram),
+            used_letters,
+            unused_letters,
+        }
+    }
+
+    fn is_pangram(word: &str, allowed: &HashSet<char>) -> bool {
+        let distinct: HashSet<char> = word
+            .to_lowercase()
+            .chars()
+            .filter(|ch| !CONNECTORS.contains(ch))
+            .collect();
+        !allowed.is_empty() && distinct == *allowed
+    }
+
+    /// A pangram with no repeated letter. Letters are compared in lowercase,
+    /// so a start-only letter and its anywhere variant count as one.
+    fn is_perfect_pangram(word: &str, allowed: &HashSet<char>) -> bool {
+        let letters = word
+            .to_lowercase()
+            .chars()
+            .filter(|ch| !CONNECTORS.contains(ch))
+            .count();
+        letters == allowed.len() && Self::is_pangram(word, allowed)
+    }
+
+    fn score(length: usize, is_pangram: bool) -> u32 {
+        let base = if length <= 4 { 1 } else { length as u32 };
+        if is_pangram {
+            base + PANGRAM_BONUS
+        } else {
+            base
+        }
+    }
+
+    /// Lazily walk the trie, yielding matching words one at a time.
+    ///
+    /// Applies the same constraints as [`Solver::solve`] except `max_results`,
+    /// which needs the full result set to rank. Words are yielded in trie order.
+    /// An invalid configuration yields no words; call `solve` to get the error.
+    pub fn iter_solutions<'a>(
+        &'a self,
+        dictionary: &'a Dictionary,
+    ) -> impl Iterator<Item = String> + 'a {
+        match self.constraints() {
+            Ok(constraints) => Solutions::new(constraints, &dictionary.root),
+            Err(e) => {
+                log::warn!("Cannot iterate solutions: {}", e);
+                Solutions::empty()
+            }
+        }
+    }
+
+    /// Resolve the config into the constraints applied during search.
+    fn constraints(&self) -> Result<Constraints, SbsError> {
+        // Mode rules (e.g. NYT's single center letter) apply even to
+        // configs that skipped `Config::validate`.
+        self.config.validate_mode()?;
+
+        let LetterSets {
+            allowed,
+            anywhere,
+            required,
+            required_start,
+        } = self.letter_sets()?;
+
+        let pattern = match self.config.pattern.as_deref() {
+            Some(p) => Some(Self::compile_pattern(p)?),
+            None => None,
+        };
+
+        let required_min = self
+            .config
+            .required_min
+            .map_or(required.len(), |n| n.min(required.len()));
+
+        Ok(Constraints {
+            allowed,
+            anywhere,
+            required,
+            required_min,
+            required_start,
+            case_sensitive: self.config.case_sensitive.unwrap_or(false),
+            min_len: self.config.min_length(),
+            max_len: self.config.maximal_word_length.unwrap_or(usize::MAX),
+            max_repeats: self.config.repeat_policy().limit(),
+            min_distinct: self.config.min_distinct_letters.unwrap_or(0),
+            pattern,
+            // Trie words are lowercase; case-sensitive mode only governs
+            // letter positions, so affixes always compare in lowercase.
+            prefix: lowercase_option(&self.config.starts_with).chars().collect(),
+            suffix: lowercase_option(&self.config.ends_with),
+            infix: lowercase_option(&self.config.contains),
+            // Matched against the lowercase trie form, so "Paris" blocks the
+            // word even when results preserve dictionary spelling.
+            blocklist: lowercase_words(&self.config.blocklist).unwrap_or_default(),
+            allowlist: lowercase_words(&self.config.allowlist_only),
+            preserve_case: self.config.preserve_case.unwrap_or(false),
+            allow_punctuation: self.config.allow_punctuation.unwrap_or(false),
+        })
+    }
+
+    /// Compile a result pattern. Letters-and-underscores templates such as
+    /// `c__e` match whole words with `_` as a single-letter wildcard; anything
+    /// else is an unanchored regex (anchor with `^`/`$` for a full-word match).
+    pub(crate) fn compile_pattern(pattern: &str) -> Result<Regex, SbsError> {
+        let is_template =
+            pattern.contains('_') && pattern.chars().all(|c| c == '_' || c.is_alphabetic());
+        let source = if is_template {
+            format!("^{}$", pattern.replace('_', "."))
+        } else {
+            pattern.to_string()
+        };
+        Regex::new(&source)
+            .map_err(|e| SbsError::ConfigError(format!("Invalid pattern '{}': {}", pattern, e)))
+    }
+
+    /// Derive the allowed, anywhere and required letter sets from the config.
+    fn letter_sets(&self) -> Result<LetterSets, SbsError> {
         let case_sensitive = self.config.case_sensitive.unwrap_or(false);
 
         let letters_str = self
@@ -35,15 +886,37 @@ impl Solver {
             .letters
             .as_ref()
             .ok_or(SbsError::ConfigError("No letters provided".to_string()))?;
+        if letters_str.trim().is_empty() {
+            return Err(SbsError::ConfigError(
+                "letters must not be empty".to_string(),
+            ));
+        }
 
+        // An absent `present` requires nothing, but an empty one is a mistake
+        if self
+            .config
+            .present
+            .as_deref()
+            .is_some_and(|p| p.trim().is_empty())
+        {
+            return Err(SbsError::ConfigError(
+                "present must not be empty".to_string(),
+            ));
+        }
         let empty = String::new();
         let required_str = self.config.present.as_ref().unwrap_or(&empty);
 
-        let min_len = self.config.minimal_word_length.unwrap_or(4);
-        let max_len = self.config.maximal_word_length.unwrap_or(usize::MAX);
-        let max_repeats = self.config.repeats;
-
-        let (allowed_chars, anywhere_chars, required_chars, required_start) = if case_sensitive {
+        // Excluded letters are matched case-insensitively and win over `letters`
+        let excluded: HashSet<char> = self
+            .config
+            .excluded
+            .as_deref()
+            .unwrap_or("")
+            .to_lowercase()
+            .chars()
+            .collect();
+
+        let mut sets = if case_sensitive {
             // Uppercase letters in `letters` can only appear at position 0
             let mut start_only: HashSet<char> = HashSet::new();
             let mut anywhere: HashSet<char> = HashSet::new();
@@ -75,34 +948,28 @@ impl Solver {
                 }
             }
 
-            (allowed, anywhere, required, req_start)
+            LetterSets {
+                allowed,
+                anywhere,
+                required,
+                required_start: req_start,
+            }
         } else {
             let lowered = letters_str.to_lowercase();
             let allowed: HashSet<char> = lowered.chars().collect();
             let anywhere = allowed.clone();
             let required: HashSet<char> = required_str.to_lowercase().chars().collect();
-            (allowed, anywhere, required, None)
-        };
-
-        let mut results = HashSet::new();
-
-        let mut ctx = SearchContext {
-            allowed: &allowed_chars,
-            anywhere: &anywhere_chars,
-            required: &required_chars,
-            required_start,
-            case_sensitive,
-            min_len,
-            max_len,
-            max_repeats,
-            results: &mut results,
+            LetterSets {
+                allowed,
+                anywhere,
+                required,
+                required_start: None,
+            }
         };
 
-        let mut char_counts = HashMap::new();
-
-        Self::find_words(&dictionary.root, String::new(), &mut char_counts, &mut ctx);
-
-        Ok(results)
+        sets.allowed.retain(|ch| !excluded.contains(ch));
+        sets.anywhere.retain(|ch| !excluded.contains(ch));
+        Ok(sets)
     }
 
     fn find_words(
@@ -111,52 +978,36 @@ impl Solver {
         char_counts: &mut HashMap<char, usize>,
         ctx: &mut SearchContext,
     ) {
-        if current_word.len() > ctx.max_len {
-            return;
+        #[cfg(test)]
+        {
+            ctx.visited += 1;
         }
 
-        // Check Valid Word
-        if node.is_end_of_word && current_word.len() >= ctx.min_len {
-            let mut all_req_present = true;
-            for req in ctx.required {
-                if *char_counts.get(req).unwrap_or(&0) == 0 {
-                    all_req_present = false;
-                    break;
-                }
-            }
-            // If case-sensitive and required_start is set, first char must match
-            if all_req_present {
-                if let Some(start_char) = ctx.required_start {
-                    if !current_word.starts_with(start_char) {
-                        all_req_present = false;
-                    }
-                }
-            }
-            if all_req_present {
-                ctx.results.insert(current_word.clone());
+        if let Some(deadline) = ctx.deadline {
+            if ctx.nodes.is_multiple_of(DEADLINE_CHECK_INTERVAL) && Instant::now() >= deadline {
+                ctx.timed_out = true;
             }
         }
+        ctx.nodes += 1;
+        if ctx.timed_out {
+            return;
+        }
+
+        // Check Valid Word (`accepts` rejects anything longer than max_len)
+        if node.is_end_of_word && ctx.constraints.accepts(&current_word, char_counts) {
+            ctx.results
+                .insert(ctx.constraints.emit(node, &current_word));
+        }
 
+        // No child can yield an in-range word once we are at max_len
         let depth = current_word.len();
+        if depth >= ctx.constraints.max_len {
+            return;
+        }
 
         // Recursive Backtracking
         for (ch, next_node) in &node.children {
-            // In case-sensitive mode, start-only chars can only appear at depth 0
-            let char_allowed = if ctx.case_sensitive && depth > 0 {
-                ctx.anywhere.contains(ch)
-            } else {
-                ctx.allowed.contains(ch)
-            };
-
-            if char_allowed {
-                // Check repetition limit
-                let count = *char_counts.get(ch).unwrap_or(&0);
-                if let Some(limit) = ctx.max_repeats {
-                    if count >= limit {
-                        continue;
-                    }
-                }
-
+            if ctx.constraints.can_extend(depth, *ch, char_counts) {
                 let mut next_word = current_word.clone();
                 next_word.push(*ch);
                 *char_counts.entry(*ch).or_insert(0) += 1;
@@ -164,321 +1015,1690 @@ impl Solver {
                 Self::find_words(next_node, next_word, char_counts, ctx);
 
                 *char_counts.entry(*ch).or_insert(0) -= 1;
+                if ctx.timed_out {
+                    return;
+                }
+            }
+            if depth == 0 {
+                (ctx.on_progress)(ctx.results.len());
             }
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Remembers solve results per puzzle so identical requests skip the trie
+/// search.
+///
+/// Results are keyed by every config field that affects which words are
+/// found (so changing only the sort order or output still hits), and the
+/// whole cache is dropped when it is used with a different
+/// [`Dictionary::revision`]. Errors, including timeouts, are not cached.
+pub struct CachingSolver {
+    capacity: usize,
+    cache: Mutex<SolveCache>,
+}
+
+#[derive(Default)]
+struct SolveCache {
+    revision: Option<u64>,
+    results: HashMap<String, HashSet<String>>,
+}
+
+impl CachingSolver {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_SOLVE_CACHE_CAPACITY)
+    }
+
+    /// Keep at most `capacity` puzzles; the cache is cleared when it fills.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            cache: Mutex::new(SolveCache::default()),
+        }
+    }
+
+    /// Like [`Solver::solve`], reusing an earlier result for the same puzzle.
+    pub fn solve(
+        &self,
+        config: &Config,
+        dictionary: &Dictionary,
+    ) -> Result<HashSet<String>, SbsError> {
+        self.cached(config, dictionary, || {
+            Solver::from_config_ref(config).solve(dictionary)
+        })
+    }
+
+    /// Like [`Solver::solve_sorted_with_deadline`]; only a cache miss is
+    /// bounded by `deadline`.
+    pub fn solve_sorted_with_deadline(
+        &self,
+        config: &Config,
+        dictionary: &Dictionary,
+        deadline: Instant,
+    ) -> Result<Vec<String>, SbsError> {
+        let solver = Solver::from_config_ref(config);
+        let mut words: Vec<String> = self
+            .cached(config, dictionary, || {
+                solver.solve_with_deadline(dictionary, deadline)
+            })?
+            .into_iter()
+            .collect();
+        solver.sort_by_order(&mut words, Some(dictionary));
+        Ok(words)
+    }
+
+    /// Number of puzzles currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn cached(
+        &self,
+        config: &Config,
+        dictionary: &Dictionary,
+        search: impl FnOnce() -> Result<HashSet<String>, SbsError>,
+    ) -> Result<HashSet<String>, SbsError> {
+        let key = Self::key(config);
+        let revision = dictionary.revision();
+        {
+            let mut cache = self.lock();
+            if cache.revision != Some(revision) {
+                cache.results.clear();
+                cache.revision = Some(revision);
+            }
+            if let Some(words) = cache.results.get(&key) {
+                return Ok(words.clone());
+            }
+        }
+
+        // Search without the lock so other puzzles are not held up
+        let words = search()?;
+        let mut cache = self.lock();
+        if cache.revision == Some(revision) && self.capacity > 0 {
+            if cache.results.len() >= self.capacity {
+                cache.results.clear();
+            }
+            cache.results.insert(key, words.clone());
+        }
+        Ok(words)
+    }
+
+    /// The config with every field that cannot change the word set cleared.
+    fn key(config: &Config) -> String {
+        let mut config = config.clone();
+        config.output = None;
+        config.sort_order = None;
+        config.group_inflections = None;
+        config.dictionary = PathBuf::new();
+        #[cfg(feature = "validator")]
+        {
+            config.validator = None;
+            config.api_key = None;
+            config.validator_url = None;
+        }
+        format!("{:?}", config)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SolveCache> {
+        // A panic elsewhere cannot leave the map half-updated, so recover it
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for CachingSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn lowercase_option(value: &Option<String>) -> String {
+    value.as_deref().unwrap_or("").to_lowercase()
+}
+
+fn lowercase_words(words: &Option<Vec<String>>) -> Option<HashSet<String>> {
+    words
+        .as_ref()
+        .map(|list| list.iter().map(|w| w.trim().to_lowercase()).collect())
+}
+
+/// One level of the explicit DFS stack used by [`Solutions`].
+struct Frame<'a> {
+    node: &'a TrieNode,
+    children: std::collections::hash_map::Iter<'a, char, TrieNode>,
+    visited: bool,
+}
+
+impl<'a> Frame<'a> {
+    fn new(node: &'a TrieNode) -> Self {
+        Self {
+            node,
+            children: node.children.iter(),
+            visited: false,
+        }
+    }
+}
+
+/// Lazy depth-first iterator over solutions, returned by [`Solver::iter_solutions`].
+struct Solutions<'a> {
+    constraints: Option<Constraints>,
+    stack: Vec<Frame<'a>>,
+    current_word: String,
+    char_counts: HashMap<char, usize>,
+}
+
+impl<'a> Solutions<'a> {
+    fn new(constraints: Constraints, root: &'a TrieNode) -> Self {
+        Self {
+            constraints: Some(constraints),
+            stack: vec![Frame::new(root)],
+            current_word: String::new(),
+            char_counts: HashMap::new(),
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            constraints: None,
+            stack: Vec::new(),
+            current_word: String::new(),
+            char_counts: HashMap::new(),
+        }
+    }
+}
+
+impl Iterator for Solutions<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let constraints = self.constraints.as_ref()?;
+        loop {
+            let frame = self.stack.last_mut()?;
+
+            if !frame.visited {
+                frame.visited = true;
+                if frame.node.is_end_of_word
+                    && constraints.accepts(&self.current_word, &self.char_counts)
+                {
+                    return Some(constraints.emit(frame.node, &self.current_word));
+                }
+            }
+
+            let depth = self.current_word.len();
+            match frame.children.next() {
+                Some((ch, child)) => {
+                    if depth < constraints.max_len
+                        && constraints.can_extend(depth, *ch, &self.char_counts)
+                    {
+                        self.current_word.push(*ch);
+                        *self.char_counts.entry(*ch).or_insert(0) += 1;
+                        self.stack.push(Frame::new(child));
+                    }
+                }
+                None => {
+                    self.stack.pop();
+                    if let Some(ch) = self.current_word.pop() {
+                        *self.char_counts.entry(ch).or_insert(0) -= 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PuzzleMode, RepeatPolicy};
+    use std::time::Duration;
+
+    #[test]
+    fn test_solver_basic() {
+        let config = Config::new().with_letters("abcdefg").with_present("a");
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["bad", "fade", "faced", "zzzz", "bed"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(results.contains("fade"));
+        assert!(results.contains("faced"));
+        assert!(!results.contains("bad"));
+        assert!(!results.contains("zzzz"));
+    }
+
+    #[test]
+    fn test_solver_multiple_required_letters() {
+        let config = Config::new().with_letters("abcdefg").with_present("af");
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["fade", "faced", "bead", "cafe", "face"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(results.contains("fade"), "contains both a and f");
+        assert!(results.contains("faced"), "contains both a and f");
+        assert!(results.contains("cafe"), "contains both a and f");
+        assert!(results.contains("face"), "contains both a and f");
+        assert!(!results.contains("bead"), "missing f");
+    }
+
+    #[test]
+    fn test_solver_min_length() {
+        let mut config = Config::new().with_letters("abcde").with_present("a");
+        config.minimal_word_length = Some(5);
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["abcd", "abcde", "ace", "abcdef"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(
+            !results.contains("abcd"),
+            "4-letter word should be excluded with min=5"
+        );
+        assert!(
+            results.contains("abcde"),
+            "5-letter word should be included"
+        );
+        assert!(!results.contains("ace"), "3-letter word should be excluded");
+    }
+
+    #[test]
+    fn test_solver_max_length() {
+        let mut config = Config::new().with_letters("abcde").with_present("a");
+        config.minimal_word_length = Some(2);
+        config.maximal_word_length = Some(4);
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["ab", "abc", "abcd", "abcde"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(
+            results.contains("abcd"),
+            "4-letter word should be included with max=4"
+        );
+        assert!(
+            !results.contains("abcde"),
+            "5-letter word should be excluded with max=4"
+        );
+    }
+
+    #[test]
+    fn test_max_length_prunes_deep_branches() {
+        let mut config = Config::new().with_letters("abcdefg").with_present("a");
+        config.minimal_word_length = Some(3);
+        config.maximal_word_length = Some(3);
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["abcdefgabcdefg", "gfedcbagfedcba", "bad", "abcd"]);
+
+        let constraints = solver.constraints().unwrap();
+        let mut results = HashSet::new();
+        let mut ctx = SearchContext {
+            constraints: &constraints,
+            results: &mut results,
+            on_progress: &|_| {},
+            deadline: None,
+            nodes: 0,
+            timed_out: false,
+            visited: 0,
+        };
+        Solver::find_words(&dict.root, String::new(), &mut HashMap::new(), &mut ctx);
+        let visited = ctx.visited;
+
+        assert_eq!(results, HashSet::from(["bad".to_string()]));
+        // Root plus one node per distinct prefix of length 1..=3: "a", "ab",
+        // "abc", "g", "gf", "gfe", "b", "ba", "bad"
+        assert_eq!(visited, 10);
+    }
+
+    #[test]
+    fn test_solver_min_and_max_length() {
+        let mut config = Config::new().with_letters("abcde").with_present("a");
+        config.minimal_word_length = Some(3);
+        config.maximal_word_length = Some(4);
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["ab", "abc", "abcd", "abcde"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(!results.contains("ab"), "2-letter word excluded");
+        assert!(results.contains("abc"), "3-letter word included");
+        assert!(results.contains("abcd"), "4-letter word included");
+        assert!(!results.contains("abcde"), "5-letter word excluded");
+    }
+
+    #[test]
+    fn test_solver_min_length_three() {
+        let config = Config::new()
+            .with_letters("abcde")
+            .with_present("a")
+            .with_min_length(3);
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["ab", "abc", "cab", "bead"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(
+            results.contains("abc"),
+            "3-letter words included with min=3"
+        );
+        assert!(results.contains("cab"));
+        assert!(results.contains("bead"));
+        assert!(!results.contains("ab"));
+    }
+
+    #[test]
+    fn test_solver_default_min_length_when_unset() {
+        // Configs loaded from files may leave the field unset
+        let mut config = Config::new().with_letters("abcde").with_present("a");
+        config.minimal_word_length = None;
+
+        let dict = Dictionary::from_words(&["abc", "abcd"]);
+        let results = Solver::new(config).solve(&dict).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert!(results.contains("abcd"), "falls back to DEFAULT_MIN_LENGTH");
+    }
+
+    #[test]
+    fn test_solver_default_min_length() {
+        let config = Config::new().with_letters("abcde").with_present("a");
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["ab", "abc", "abcd", "abcde"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(
+            !results.contains("ab"),
+            "2-letter word excluded by default min=4"
+        );
+        assert!(
+            !results.contains("abc"),
+            "3-letter word excluded by default min=4"
+        );
+        assert!(
+            results.contains("abcd"),
+            "4-letter word included by default min=4"
+        );
+    }
+
+    #[test]
+    fn test_solver_repeats() {
+        let mut config = Config::new().with_letters("ab").with_present("a");
+        config.repeats = Some(RepeatPolicy::Max(1));
+        config.minimal_word_length = Some(2);
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["aa", "ab"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(results.contains("ab"), "Result should contain 'ab'");
+        assert!(
+            !results.contains("aa"),
+            "Result should NOT contain 'aa' due to repeat limit"
+        );
+    }
+
+    // --- Case-sensitive mode tests ---
+
+    #[test]
+    fn test_solver_case_insensitive_default() {
+        // Uppercase input should be normalized when case_sensitive is off (default)
+        let config = Config::new().with_letters("ABCDEFG").with_present("A");
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["fade", "faced", "bad"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(
+            results.contains("fade"),
+            "uppercase input should be normalized"
+        );
+        assert!(results.contains("faced"));
+    }
+
+    #[test]
+    fn test_solver_case_sensitive_start_only() {
+        // 'W' uppercase means 'w' can only appear at position 0
+        // 'a', 'r', 'e' are lowercase, can appear anywhere
+        let mut config = Config::new().with_letters("Ware").with_present("a");
+        config.case_sensitive = Some(true);
+        config.minimal_word_length = Some(3);
+
+        let solver = Solver::new(config);
+        // "war" starts with w — OK
+        // "raw" has w not at start — should be excluded
+        // "ware" starts with w — OK
+        // "area" has no w — OK (w is not required)
+        let dict = Dictionary::from_words(&["war", "raw", "ware", "area", "aw"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(results.contains("war"), "w at position 0 is allowed");
+        assert!(!results.contains("raw"), "w at position > 0 is not allowed");
+        assert!(results.contains("ware"), "w at position 0 is allowed");
+        assert!(
+            results.contains("area"),
+            "no w needed, all letters are anywhere-letters"
+        );
+    }
+
+    #[test]
+    fn test_solver_case_sensitive_required_start() {
+        // Uppercase in present means that letter must be at position 0
+        let mut config = Config::new().with_letters("Ware").with_present("W");
+        config.case_sensitive = Some(true);
+        config.minimal_word_length = Some(3);
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["war", "raw", "ware", "area", "era"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(
+            results.contains("war"),
+            "starts with w, w is required start"
+        );
+        assert!(!results.contains("raw"), "w not at start");
+        assert!(results.contains("ware"), "starts with w");
+        assert!(!results.contains("area"), "does not start with w");
+        assert!(!results.contains("era"), "does not start with w");
+    }
+
+    #[test]
+    fn test_solver_case_sensitive_both_cases() {
+        // Both 'W' (start-only) and 'w' (anywhere) in letters
+        let mut config = Config::new().with_letters("Wware").with_present("a");
+        config.case_sensitive = Some(true);
+        config.minimal_word_length = Some(3);
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["war", "raw", "ware", "awe"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        // Both 'W' (start-only) and 'w' (anywhere) contribute to allowed.
+        // 'w' (lowercase) is in anywhere, so w can appear at any position.
+        assert!(results.contains("war"));
+        assert!(results.contains("raw"), "lowercase w allows w anywhere");
+        assert!(results.contains("ware"));
+        assert!(results.contains("awe"), "lowercase w allows w anywhere");
+    }
+
+    #[test]
+    fn test_solver_case_sensitive_walrus_scenario() {
+        let mut config = Config::new().with_letters("Walrus").with_present("Wl");
+        config.case_sensitive = Some(true);
+        config.minimal_word_length = Some(4);
+
+        let solver = Solver::new(config);
+        let dict =
+            Dictionary::from_words(&["awls", "laws", "slaw", "wall", "walls", "walrus", "lure"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(!results.contains("awls"), "awls does not start with w");
+        assert!(!results.contains("laws"), "laws does not start with w");
+        assert!(!results.contains("slaw"), "slaw does not start with w");
+        assert!(
+            !results.contains("lure"),
+            "lure does not contain l... wait it does, but no w start"
+        );
+        assert!(
+            results.contains("wall"),
+            "wall starts with w and contains l"
+        );
+        assert!(
+            results.contains("walls"),
+            "walls starts with w and contains l"
+        );
+        assert!(
+            results.contains("walrus"),
+            "walrus starts with w and contains l"
+        );
+    }
+
+    #[test]
+    fn test_solver_case_sensitive_all_lowercase_no_constraint() {
+        // When case_sensitive=true but all letters are lowercase, no positional constraint applies
+        let mut config = Config::new().with_letters("walrus").with_present("wl");
+        config.case_sensitive = Some(true);
+        config.minimal_word_length = Some(4);
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["awls", "laws", "wall", "walrus"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(
+            results.contains("awls"),
+            "all lowercase: w is an anywhere letter, no start constraint"
+        );
+        assert!(
+            results.contains("laws"),
+            "all lowercase: w is an anywhere letter"
+        );
+        assert!(results.contains("wall"));
+        assert!(results.contains("walrus"));
+    }
+
+    #[test]
+    fn test_solver_case_sensitive_multiple_uppercase_required_error() {
+        let mut config = Config::new().with_letters("ABcde").with_present("AB");
+        config.case_sensitive = Some(true);
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["abcd"]);
+
+        let result = solver.solve(&dict);
+        assert!(
+            result.is_err(),
+            "multiple uppercase required letters should error"
+        );
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("At most one uppercase"));
+    }
+
+    #[test]
+    fn test_solver_no_required_letters() {
+        let mut config = Config::new().with_letters("ab");
+        config.minimal_word_length = Some(1);
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["a", "ab", "ba", "b", "abc", "ca"]);
+        let result = solver.solve(&dict).unwrap();
+        // All words using only a and b should match
+        assert!(result.contains("a"));
+        assert!(result.contains("ab"));
+        assert!(result.contains("ba"));
+        assert!(result.contains("b"));
+        // Words with letters outside available set should not match
+        assert!(!result.contains("abc"));
+        assert!(!result.contains("ca"));
+    }
+
+    #[test]
+    fn test_solver_pattern_anchored() {
+        let mut config = Config::new().with_letters("abcde").with_present("a");
+        config.pattern = Some("^a.*e$".to_string());
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["abide", "aced", "dace", "abbe", "bead"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(results.contains("abbe"));
+        assert!(!results.contains("aced"), "does not end with e");
+        assert!(!results.contains("dace"), "does not start with a");
+        assert!(!results.contains("bead"));
+    }
+
+    #[test]
+    fn test_solver_pattern_unanchored() {
+        let mut config = Config::new().with_letters("abcde").with_present("a");
+        config.pattern = Some("ce".to_string());
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["aced", "dace", "bead", "cede"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(results.contains("aced"), "ce matches anywhere in the word");
+        assert!(results.contains("dace"));
+        assert!(!results.contains("bead"));
+    }
+
+    #[test]
+    fn test_solver_pattern_wildcard_template() {
+        let mut config = Config::new().with_letters("abcde").with_present("a");
+        config.pattern = Some("_a_e".to_string());
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["cade", "bead", "babe", "abase", "aced"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        assert!(results.contains("cade"));
+        assert!(results.contains("babe"));
+        assert!(!results.contains("abase"), "template fixes the length");
+        assert!(!results.contains("bead"), "a must be second");
+        assert!(!results.contains("aced"));
+    }
+
+    #[test]
+    fn test_solver_starts_with_and_ends_with() {
+        let mut config = Config::new().with_letters("abcde").with_present("a");
+        config.starts_with = Some("Ba".to_string());
+        config.ends_with = Some("e".to_string());
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["babe", "bade", "bead", "abbe", "ba"]);
+
+        let results = solver.solve(&dict).expect("Solver failed");
+
+        let mut sorted: Vec<_> = results.into_iter().collect();
+        sorted.sort();
+        assert_eq!(sorted, vec!["babe", "bade"]);
+    }
+
+    #[test]
+    fn test_solver_starts_with_prunes_search() {
+        let mut config = Config::new().with_letters("abcde").with_present("a");
+        config.starts_with = Some("ca".to_string());
+
+        config.minimal_word_length = Some(3);
+        let solver = Solver::new(config);
+
+        let constraints = solver.constraints().unwrap();
+        let counts = HashMap::new();
+        assert!(constraints.can_extend(0, 'c', &counts));
+        assert!(!constraints.can_extend(0, 'a', &counts), "diverges at 0");
+        assert!(!constraints.can_extend(1, 'b', &counts), "diverges at 1");
+        assert!(constraints.can_extend(2, 'b', &counts), "past the prefix");
+
+        let dict = Dictionary::from_words(&["cade", "aced", "cab", "abcd", "cabbed", "c"]);
+        let mut results: Vec<_> = solver.iter_solutions(&dict).collect();
+        results.sort();
+        assert_eq!(results, vec!["cab", "cabbed", "cade"]);
+    }
+
+    #[test]
+    fn test_solver_affixes_case_sensitive() {
+        // 'W' may only start a word; affixes still compare in lowercase
+        let mut config = Config::new().with_letters("Ware").with_present("a");
+        config.case_sensitive = Some(true);
+        config.minimal_word_length = Some(3);
+        config.starts_with = Some("W".to_string());
+
+        let dict = Dictionary::from_words(&["war", "ware", "area", "raw"]);
+        let results = Solver::new(config.clone()).solve(&dict).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.contains("war") && results.contains("ware"));
+
+        config.starts_with = None;
+        config.ends_with = Some("w".to_string());
+        let results = Solver::new(config).solve(&dict).unwrap();
+        assert!(results.is_empty(), "w cannot end a word when start-only");
+    }
+
+    #[test]
+    fn test_solver_contains_substring() {
+        let mut config = Config::new().with_letters("rindge").with_present("i");
+        config.minimal_word_length = Some(4);
+        config.contains = Some("in".to_string());
+        let dict =
+            Dictionary::from_words(&["ring", "rind", "dine", "grid", "grin", "reigning", "gin"]);
+
+        let results = Solver::new(config.clone()).solve(&dict).unwrap();
+        assert!(results.contains("ring"));
+        assert!(results.contains("rind"));
+        assert!(results.contains("dine"));
+        assert!(results.contains("reigning"));
+        assert!(results.contains("grin"));
+        assert!(!results.contains("grid"), "i and n not contiguous");
+        assert!(!results.contains("gin"), "shorter than min length");
+
+        config.contains = Some("INE".to_string());
+        config.maximal_word_length = Some(4);
+        let results = Solver::new(config.clone()).solve(&dict).unwrap();
+        assert!(!results.contains("rind"));
+        assert_eq!(results.len(), 1);
+        assert!(results.contains("dine"));
+
+        config.case_sensitive = Some(true);
+        config.letters = Some("Rindge".to_string());
+        config.contains = Some("in".to_string());
+        let results = Solver::new(config).solve(&dict).unwrap();
+        assert!(results.contains("ring") && results.contains("rind"));
+        assert!(!results.contains("grin"), "r may only start a word");
+    }
+
+    #[test]
+    fn test_solve_grouped() {
+        let config = Config::new().with_letters("abdefgs").with_present("a");
+        let dict = Dictionary::from_words(&["fade", "faded", "fades", "bead", "beads"]);
+
+        let groups = Solver::new(config).solve_grouped(&dict).unwrap();
+
+        let stems: Vec<&str> = groups.iter().map(|g| g.stem.as_str()).collect();
+        assert_eq!(stems, vec!["bead", "fade"]);
+        assert_eq!(groups[1].forms, vec!["fade", "faded", "fades"]);
+    }
+
+    #[test]
+    fn test_solve_with_progress_reports_per_subtree() {
+        use std::cell::RefCell;
+
+        // 26 starting letters x 26 x 26 three-letter words
+        let mut words = Vec::new();
+        for a in 'a'..='z' {
+            for b in 'a'..='z' {
+                for c in 'a'..='z' {
+                    words.push(format!("{}{}{}", a, b, c));
+                }
+            }
+        }
+        let refs: Vec<&str> = words.iter().map(String::as_str).collect();
+        let dict = Dictionary::from_words(&refs);
+
+        let config = Config::new()
+            .with_letters("abcdefg")
+            .with_present("a")
+            .with_min_length(3);
+        let calls = RefCell::new(Vec::new());
+        let results = Solver::new(config)
+            .solve_with_progress(&dict, &|found| calls.borrow_mut().push(found))
+            .unwrap();
+
+        let calls = calls.into_inner();
+        assert_eq!(calls.len(), 26, "one call per top-level subtree");
+        assert!(calls.windows(2).all(|w| w[0] <= w[1]), "counts only grow");
+        assert_eq!(calls.last(), Some(&results.len()));
+        // 7^3 words from abcdefg, minus the 6^3 without an 'a'
+        assert_eq!(results.len(), 343 - 216);
+    }
+
+    #[test]
+    fn test_blocklist_removes_words() {
+        let mut config = Config::new().with_letters("abcdefg").with_present("a");
+        config.blocklist = Some(vec!["FACE".to_string(), "zzzz".to_string()]);
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["cafe", "face", "fade", "zzzz"]);
+
+        assert_eq!(solver.solve_sorted(&dict).unwrap(), ["cafe", "fade"]);
+        let lazy: HashSet<String> = solver.iter_solutions(&dict).collect();
+        assert_eq!(lazy, solver.solve(&dict).unwrap());
+    }
+
+    #[test]
+    fn test_allowlist_only_intersects() {
+        let mut config = Config::new().with_letters("abcdefg").with_present("a");
+        config.allowlist_only = Some(vec![
+            "cafe".to_string(),
+            "fade".to_string(),
+            "bed".to_string(),
+        ]);
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["cafe", "face", "fade", "bed"]);
+
+        // "bed" is allowed but fails the letter constraints
+        assert_eq!(solver.solve_sorted(&dict).unwrap(), ["cafe", "fade"]);
+    }
+
+    #[test]
+    fn test_blocklist_wins_over_allowlist() {
+        let mut config = Config::new().with_letters("abcdefg").with_present("a");
+        config.allowlist_only = Some(vec!["cafe".to_string(), "fade".to_string()]);
+        config.blocklist = Some(vec!["fade".to_string()]);
+        config.preserve_case = Some(true);
+        let solver = Solver::new(config);
+        let mut dict = Dictionary::from_words(&["face", "fade"]);
+        dict.insert_word("Cafe");
+
+        assert_eq!(solver.solve_sorted(&dict).unwrap(), ["Cafe"]);
+    }
+
+    #[test]
+    fn test_solver_from_config_ref() {
+        let config = Config::new().with_letters("abcdefg").with_present("a");
+        let dict = Dictionary::from_words(&["cafe", "face", "zzzz"]);
+
+        let borrowed = Solver::from_config_ref(&config).solve(&dict).unwrap();
+        let owned = Solver::new(config.clone()).solve(&dict).unwrap();
+
+        assert_eq!(borrowed, owned);
+        assert_eq!(config.letters.as_deref(), Some("abcdefg"), "still usable");
+    }
+
+    #[test]
+    fn test_solve_batch() {
+        let dict = Dictionary::from_words(&["cafe", "face", "fade", "bead", "glad", "gale"]);
+        let configs = [
+            Config::new().with_letters("acef").with_present("c"),
+            Config::new().with_letters("abdeg").with_present("b"),
+            Config::new().with_letters("adgl").with_present("g"),
+            Config::new().with_present("a"),
+        ];
+
+        let results = Solver::solve_batch(&dict, &configs);
+
+        assert_eq!(results.len(), 4);
+        let words = |i: usize| {
+            let mut words: Vec<String> = results[i].as_ref().unwrap().iter().cloned().collect();
+            words.sort();
+            words
+        };
+        assert_eq!(words(0), ["cafe", "face"]);
+        assert_eq!(words(1), ["bead"]);
+        assert_eq!(words(2), ["glad"]);
+        assert!(
+            matches!(results[3], Err(SbsError::ConfigError(_))),
+            "missing letters fails only its own puzzle"
+        );
+    }
+
+    #[test]
+    fn test_removed_word_leaves_results() {
+        let solver = Solver::new(Config::new().with_letters("abcdefg").with_present("a"));
+        let mut dict = Dictionary::from_words(&["cafe", "caged", "cage", "face"]);
+
+        assert!(dict.remove_word("cage"));
+        let results = solver.solve(&dict).unwrap();
+
+        assert!(!results.contains("cage"));
+        assert!(results.contains("caged"), "word through the removed prefix");
+        assert_eq!(results.len(), 3);
+    }
+
+    #[test]
+    fn test_solve_with_past_deadline_times_out() {
+        let mut words = Vec::new();
+        for a in 'a'..='g' {
+            for b in 'a'..='g' {
+                for c in 'a'..='g' {
+                    for d in 'a'..='g' {
+                        words.push(format!("{}{}{}{}", a, b, c, d));
+                    }
+                }
+            }
+        }
+        let refs: Vec<&str> = words.iter().map(String::as_str).collect();
+        let dict = Dictionary::from_words(&refs);
+        let solver = Solver::new(Config::new().with_letters("abcdefg").with_present("a"));
+
+        let started = Instant::now();
+        let result = solver.solve_with_deadline(&dict, Instant::now() - Duration::from_secs(1));
+
+        assert!(matches!(result, Err(SbsError::Timeout(_))));
+        assert!(
+            started.elapsed() < Duration::from_millis(100),
+            "returned promptly"
+        );
+    }
+
+    #[test]
+    fn test_solve_with_future_deadline_matches_solve() {
+        let config = Config::new().with_letters("abcdefg").with_present("a");
+        let dict = Dictionary::from_words(&["fade", "face", "cafe", "decaf", "zzzz"]);
+        let solver = Solver::new(config);
+        let deadline = Instant::now() + Duration::from_secs(60);
+
+        assert_eq!(
+            solver.solve_with_deadline(&dict, deadline).unwrap(),
+            solver.solve(&dict).unwrap()
+        );
+        assert_eq!(
+            solver.solve_sorted_with_deadline(&dict, deadline).unwrap(),
+            ["cafe", "decaf", "face", "fade"]
+        );
+    }
+
+    #[test]
+    fn test_length_histogram() {
+        let config = Config::new().with_letters("abcdefg").with_present("a");
+        let dict = Dictionary::from_words(&[
+            "fade", "face", "cafe", "faced", "decaf", "baggage", "bed", "zzzz",
+        ]);
+
+        let histogram = Solver::new(config).length_histogram(&dict).unwrap();
+
+        assert_eq!(histogram, BTreeMap::from([(4, 3), (5, 2), (7, 1)]));
+    }
+
+    #[test]
+    fn test_nearest_words() {
+        let solver = Solver::new(Config::new());
+        let dict = Dictionary::from_words(&["apple", "apply", "maple", "zebra", "ape"]);
+
+        assert_eq!(
+            solver.nearest_words(&dict, "aple", 1),
+            vec!["ape", "apple", "maple"]
+        );
+        assert_eq!(
+            solver.nearest_words(&dict, "aple", 2),
+            vec!["ape", "apple", "maple", "apply"]
+        );
+        assert_eq!(solver.nearest_words(&dict, "Apple", 0), vec!["apple"]);
+        assert!(solver.nearest_words(&dict, "qqqq", 2).is_empty());
+    }
+
+    #[test]
+    fn test_solve_result() {
+        let config = Config::new().with_letters("aelpt").with_present("a");
+        let dict = Dictionary::from_words(&["plate", "apple", "pale", "peel", "ale"]);
+
+        let result = Solver::new(config).solve_result(&dict).unwrap();
+
+        assert_eq!(result.words, vec!["apple", "pale", "plate"]);
+        assert_eq!(result.pangrams, vec!["plate"]);
+        assert_eq!(result.count, 3);
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "words": ["apple", "pale", "plate"],
+                "pangrams": ["plate"],
+                "count": 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_rarest_word_prefers_lowest_frequency() {
+        let source = "apple\t900\nplate\t40\nleap\t7\npale\n";
+        let dict = Dictionary::from_reader(std::io::Cursor::new(source)).unwrap();
+        let solver = Solver::new(Config::new().with_letters("aelpt").with_present("a"));
+
+        // "pale" has no weight, so the least frequent weighted word wins
+        assert_eq!(solver.rarest_word(&dict).unwrap(), Some("leap".to_string()));
+    }
+
+    #[test]
+    fn test_rarest_word_heuristic_without_frequencies() {
+        let dict = Dictionary::from_words(&["jade", "fade", "faze", "decaf", "faced", "fazed"]);
+        let solver = Solver::new(Config::new().with_letters("acdefjz").with_present("a"));
+
+        // Longest words win; "fazed" beats "decaf"/"faced" on its rare z
+        assert_eq!(
+            solver.rarest_word(&dict).unwrap(),
+            Some("fazed".to_string())
+        );
+
+        let four = ["fade", "jade", "faze"].map(String::from);
+        assert_eq!(Solver::rarest_of(&four, &dict), Some("faze".to_string()));
+        assert_eq!(Solver::rarest_of(&[], &dict), None);
+    }
+
+    #[test]
+    fn test_solve_with_bonus_splits_by_length() {
+        let dict = Dictionary::from_words(&["ape", "pea", "pa", "leap", "apple", "elk"]);
+        let mut config = Config::new().with_letters("aelp").with_present("a");
+        config.bonus_min_length = Some(3);
+
+        let (words, bonus) = Solver::new(config.clone()).solve_with_bonus(&dict).unwrap();
+        assert_eq!(words, vec!["apple", "leap"]);
+        assert_eq!(
+            bonus,
+            vec!["ape", "pea"],
+            "still bound by the required letter"
+        );
+
+        let result = Solver::new(config.clone()).solve_result(&dict).unwrap();
+        assert_eq!(result.count, 2);
+        assert_eq!(result.bonus, vec!["ape", "pea"]);
+
+        config.bonus_min_length = Some(4);
+        let (_, bonus) = Solver::new(config).solve_with_bonus(&dict).unwrap();
+        assert!(bonus.is_empty(), "no bonus window at the minimum");
+    }
+
+    #[test]
+    fn test_two_letter_counts() {
+        let config = Config::new().with_letters("abcdefg").with_present("a");
+        let dict = Dictionary::from_words(&[
+            "fade", "face", "cafe", "faced", "decaf", "baggage", "bed", "zzzz",
+        ]);
+
+        let counts = Solver::new(config).two_letter_counts(&dict).unwrap();
+
+        let expected: BTreeMap<String, usize> = [("ba", 1), ("ca", 1), ("de", 1), ("fa", 3)]
+            .into_iter()
+            .map(|(bigram, count)| (bigram.to_string(), count))
+            .collect();
+        assert_eq!(counts, expected);
+    }
+
+    #[test]
+    fn test_count_bigrams_skips_short_words_and_folds_case() {
+        let words = ["Apple", "apt", "a", "Bee"].map(String::from);
+        let counts = Solver::count_bigrams(&words);
+        assert_eq!(counts.get("ap"), Some(&2));
+        assert_eq!(counts.get("be"), Some(&1));
+        assert_eq!(counts.len(), 2);
+    }
+
+    #[test]
+    fn test_anagrams_exact_multiset() {
+        let solver = Solver::new(Config::new());
+        let dict = Dictionary::from_words(&[
+            "listen", "silent", "enlist", "tinsel", "list", "listens", "sillent", "stein",
+        ]);
+
+        let results = solver.anagrams(&dict, "Listen").unwrap();
+
+        let expected: HashSet<String> = ["listen", "silent", "enlist", "tinsel"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        assert_eq!(results, expected);
+        assert!(!results.contains("list"), "must use every letter");
+        assert!(!results.contains("sillent"), "l is available only once");
+    }
+
+    #[test]
+    fn test_words_from_letters_of_bounds_counts() {
+        let dict = Dictionary::from_words(&["bee", "bet", "beet", "bete", "tee", "be", "beeet"]);
+        let mut config = Config::new();
+        config.minimal_word_length = Some(3);
+        let solver = Solver::new(config);
+
+        let results = solver.words_from_letters_of(&dict, "bet").unwrap();
+        assert_eq!(results, HashSet::from(["bet".to_string()]));
+
+        let results = solver.words_from_letters_of(&dict, "beet").unwrap();
+        let mut sorted: Vec<_> = results.into_iter().collect();
+        sorted.sort();
+        assert_eq!(sorted, vec!["bee", "beet", "bet", "bete", "tee"]);
+        assert!(!sorted.contains(&"beeet".to_string()), "only two e's");
+    }
+
+    #[test]
+    fn test_words_from_letters_of_default_min_length() {
+        let dict = Dictionary::from_words(&["bee", "beet"]);
+        let results = Solver::new(Config::new())
+            .words_from_letters_of(&dict, "beet")
+            .unwrap();
+        assert_eq!(results, HashSet::from(["beet".to_string()]));
+    }
+
+    #[test]
+    fn test_anagrams_requires_letters() {
+        let dict = Dictionary::from_words(&["a"]);
+        let err = Solver::new(Config::new()).anagrams(&dict, " ").unwrap_err();
+        assert!(matches!(err, SbsError::ConfigError(_)));
+    }
+
+    #[test]
+    fn test_solve_rejects_empty_letters_and_present() {
+        let dict = Dictionary::from_words(&["abed"]);
+
+        let err = Solver::new(Config::new().with_letters("  ").with_present("a"))
+            .solve(&dict)
+            .unwrap_err();
+        assert!(
+            matches!(&err, SbsError::ConfigError(m) if m.contains("letters")),
+            "{}",
+            err
+        );
+
+        let err = Solver::new(Config::new().with_letters("abcde").with_present(""))
+            .solve(&dict)
+            .unwrap_err();
+        assert!(
+            matches!(&err, SbsError::ConfigError(m) if m.contains("present")),
+            "{}",
+            err
+        );
+
+        let mut config = Config::new().with_letters("abcde");
+        config.present = None;
+        assert!(
+            Solver::new(config).solve(&dict).is_ok(),
+            "absent present requires nothing"
+        );
+    }
+
+    #[test]
+    fn test_solver_invalid_pattern_error() {
+        let mut config = Config::new().with_letters("abcde").with_present("a");
+        config.pattern = Some("(ab".to_string());
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["abed"]);
+
+        let err = solver.solve(&dict).unwrap_err();
+        assert!(matches!(err, SbsError::ConfigError(_)));
+    }
+
+    #[test]
+    fn test_solve_with_metadata_pangrams() {
+        let config = Config::new().with_letters("abcdefg").with_present("a");
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["fade", "cabbaged", "bagfaced", "decaf"]);
+
+        let results = solver.solve_with_metadata(&dict).expect("Solver failed");
+
+        let words: Vec<&str> = results.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, vec!["bagfaced", "cabbaged", "decaf", "fade"]);
+
+        let bagfaced = &results[0];
+        assert!(bagfaced.is_pangram, "uses all seven letters");
+        assert_eq!(bagfaced.length, 8);
+        assert!(!results[1].is_pangram, "cabbaged lacks f");
+        assert!(!results[3].is_pangram);
+        assert_eq!(results[3].length, 4);
+    }
+
+    #[test]
+    fn test_solve_with_metadata_perfect_pangrams() {
+        let mut config = Config::new().with_letters("abcdef").with_present("a");
+        config.minimal_word_length = Some(5);
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["faced", "decafb", "bedfaced"]);
+
+        let results = solver.solve_with_metadata(&dict).unwrap();
+        let word = |w: &str| results.iter().find(|r| r.word == w).unwrap();
+
+        assert!(!word("faced").is_pangram, "lacks b");
+        assert!(!word("faced").is_perfect_pangram);
+        assert!(word("decafb").is_pangram);
+        assert!(
+            word("decafb").is_perfect_pangram,
+            "each letter exactly once"
+        );
+        assert!(word("bedfaced").is_pangram);
+        assert!(!word("bedfaced").is_perfect_pangram, "repeats d and e");
+        assert!(solver.word_is_perfect_pangram("DECAFB"));
+        assert!(!solver.word_is_perfect_pangram("bedfaced"));
+    }
+
+    #[test]
+    fn test_perfect_pangram_case_sensitive() {
+        // 'W' is start-only; counted once like any other letter
+        let mut config = Config::new().with_letters("Walrus").with_present("W");
+        config.case_sensitive = Some(true);
+        let dict = Dictionary::from_words(&["walrus", "walruss"]);
+
+        let results = Solver::new(config).solve_with_metadata(&dict).unwrap();
+
+        assert!(results[0].is_perfect_pangram, "walrus");
+        assert!(
+            results[1].is_pangram && !results[1].is_perfect_pangram,
+            "walruss"
+        );
+    }
+
+    #[test]
+    fn test_solve_with_metadata_letter_usage() {
+        let mut config = Config::new().with_letters("Walrus").with_present("Wl");
+        config.case_sensitive = Some(true);
+        let dict = Dictionary::from_words(&["wall", "walls", "walrus"]);
+
+        let results = Solver::new(config).solve_with_metadata(&dict).unwrap();
+
+        let wall = &results[0];
+        assert_eq!(wall.used_letters, BTreeSet::from(['a', 'l', 'w']));
+        assert_eq!(wall.unused_letters, BTreeSet::from(['r', 's', 'u']));
+        let walls = &results[1];
+        assert_eq!(walls.unused_letters, BTreeSet::from(['r', 'u']));
+        let walrus = &results[2];
+        assert!(walrus.is_pangram);
+        assert!(walrus.unused_letters.is_empty());
+        assert_eq!(walrus.used_letters.len(), 6);
+    }
+
+    #[test]
+    fn test_solve_with_metadata_case_sensitive_pangram() {
+        // 'W' is start-only but still counts towards the pangram letter set
+        let mut config = Config::new().with_letters("Wal").with_present("a");
+        config.case_sensitive = Some(true);
+        config.minimal_word_length = Some(3);
+
+        let solver = Solver::new(config);
+        let dict = Dictionary::from_words(&["wall", "alla"]);
+
+        let results = solver.solve_with_metadata(&dict).expect("Solver failed");
+
+        let wall = results.iter().find(|w| w.word == "wall").unwrap();
+        assert!(wall.is_pangram);
+        let alla = results.iter().find(|w| w.word == "alla").unwrap();
+        assert!(!alla.is_pangram);
+    }
+
+    #[test]
+    fn test_score_word() {
+        let config = Config::new().with_letters("abcdefg").with_present("a");
+        let solver = Solver::new(config);
+
+        assert_eq!(solver.score_word("fade"), 1, "4-letter word");
+        assert_eq!(solver.score_word("decade"), 6, "6-letter non-pangram");
+        assert_eq!(solver.score_word("bagfced"), 14, "7-letter pangram");
+    }
 
     #[test]
-    fn test_solver_basic() {
+    fn test_solve_summary_total_score() {
         let config = Config::new().with_letters("abcdefg").with_present("a");
 
         let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["bad", "fade", "faced", "zzzz", "bed"]);
+        let dict = Dictionary::from_words(&["fade", "decade", "bagfaced"]);
 
-        let results = solver.solve(&dict).expect("Solver failed");
+        let summary = solver.solve_summary(&dict).expect("Solver failed");
 
-        assert!(results.contains("fade"));
-        assert!(results.contains("faced"));
-        assert!(!results.contains("bad"));
-        assert!(!results.contains("zzzz"));
+        assert_eq!(summary.words.len(), 3);
+        assert_eq!(summary.total_score, 1 + 6 + 15);
     }
 
-    #[test]
-    fn test_solver_multiple_required_letters() {
-        let config = Config::new().with_letters("abcdefg").with_present("af");
+    // --- Required-min tests ---
 
+    #[test]
+    fn test_required_min_accepts_partial_matches() {
+        let mut config = Config::new().with_letters("abcdefz").with_present("afz");
+        config.required_min = Some(2);
         let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["fade", "faced", "bead", "cafe", "face"]);
+        let dict = Dictionary::from_words(&["fade", "faze", "bead", "daze", "zzzz"]);
 
-        let results = solver.solve(&dict).expect("Solver failed");
+        let results = solver.solve_sorted(&dict).unwrap();
 
-        assert!(results.contains("fade"), "contains both a and f");
-        assert!(results.contains("faced"), "contains both a and f");
-        assert!(results.contains("cafe"), "contains both a and f");
-        assert!(results.contains("face"), "contains both a and f");
-        assert!(!results.contains("bead"), "missing f");
+        // "fade" has just a+f; "bead" and "zzzz" have one required letter each
+        assert_eq!(results, ["daze", "fade", "faze"]);
     }
 
     #[test]
-    fn test_solver_min_length() {
-        let mut config = Config::new().with_letters("abcde").with_present("a");
-        config.minimal_word_length = Some(5);
+    fn test_required_min_unset_requires_all() {
+        let config = Config::new().with_letters("abcdefz").with_present("afz");
+        let dict = Dictionary::from_words(&["fade", "faze"]);
 
-        let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["abcd", "abcde", "ace", "abcdef"]);
+        assert_eq!(Solver::new(config).solve_sorted(&dict).unwrap(), ["faze"]);
+    }
 
-        let results = solver.solve(&dict).expect("Solver failed");
+    // --- Puzzle mode tests ---
 
-        assert!(
-            !results.contains("abcd"),
-            "4-letter word should be excluded with min=5"
-        );
-        assert!(
-            results.contains("abcde"),
-            "5-letter word should be included"
-        );
-        assert!(!results.contains("ace"), "3-letter word should be excluded");
+    #[test]
+    fn test_solver_nyt_mode() {
+        let mut config = Config::new().with_letters("abcdefg").with_present("a");
+        config.mode = Some(PuzzleMode::Nyt);
+        let dict = Dictionary::from_words(&["bad", "fade", "cabbage", "bed"]);
+
+        let results = Solver::new(config.clone()).solve(&dict).unwrap();
+        assert!(results.contains("fade"));
+        assert!(results.contains("cabbage"), "letters may repeat");
+        assert!(!results.contains("bad"), "too short");
+        assert!(!results.contains("bed"), "missing center letter");
+
+        let err = Solver::new(config.with_present("af"))
+            .solve(&dict)
+            .unwrap_err();
+        assert!(err.to_string().contains("exactly one center letter"));
     }
 
+    // --- Letter constraint tests ---
+
     #[test]
-    fn test_solver_max_length() {
-        let mut config = Config::new().with_letters("abcde").with_present("a");
-        config.minimal_word_length = Some(2);
-        config.maximal_word_length = Some(4);
+    fn test_solver_excluded_letters() {
+        let mut config = Config::new().with_letters("abcdefg").with_present("a");
+        config.excluded = Some("F".to_string());
 
         let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["ab", "abc", "abcd", "abcde"]);
+        let dict = Dictionary::from_words(&["fade", "bead", "cage", "decaf"]);
 
         let results = solver.solve(&dict).expect("Solver failed");
 
-        assert!(
-            results.contains("abcd"),
-            "4-letter word should be included with max=4"
-        );
-        assert!(
-            !results.contains("abcde"),
-            "5-letter word should be excluded with max=4"
-        );
+        assert!(!results.contains("fade"), "f is excluded");
+        assert!(!results.contains("decaf"), "f is excluded");
+        assert!(results.contains("cage"));
     }
 
     #[test]
-    fn test_solver_min_and_max_length() {
-        let mut config = Config::new().with_letters("abcde").with_present("a");
-        config.minimal_word_length = Some(3);
-        config.maximal_word_length = Some(4);
+    fn test_solver_excluded_required_letter_yields_nothing() {
+        let mut config = Config::new().with_letters("abcdefg").with_present("a");
+        config.excluded = Some("a".to_string());
 
         let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["ab", "abc", "abcd", "abcde"]);
-
-        let results = solver.solve(&dict).expect("Solver failed");
+        let dict = Dictionary::from_words(&["fade", "bead", "cage"]);
 
-        assert!(!results.contains("ab"), "2-letter word excluded");
-        assert!(results.contains("abc"), "3-letter word included");
-        assert!(results.contains("abcd"), "4-letter word included");
-        assert!(!results.contains("abcde"), "5-letter word excluded");
+        let results = solver.solve(&dict).expect("excluded letters never error");
+        assert!(results.is_empty());
     }
 
     #[test]
-    fn test_solver_default_min_length() {
-        let config = Config::new().with_letters("abcde").with_present("a");
+    fn test_solver_max_results_keeps_longest() {
+        let words: Vec<String> = (0..100)
+            .map(|i| format!("a{}", "b".repeat(i + 3)))
+            .collect();
+        let word_refs: Vec<&str> = words.iter().map(String::as_str).collect();
+
+        let mut config = Config::new().with_letters("ab").with_present("a");
+        config.max_results = Some(10);
 
         let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["ab", "abc", "abcd", "abcde"]);
+        let dict = Dictionary::from_words(&word_refs);
 
         let results = solver.solve(&dict).expect("Solver failed");
 
-        assert!(
-            !results.contains("ab"),
-            "2-letter word excluded by default min=4"
-        );
-        assert!(
-            !results.contains("abc"),
-            "3-letter word excluded by default min=4"
-        );
-        assert!(
-            results.contains("abcd"),
-            "4-letter word included by default min=4"
-        );
+        assert_eq!(results.len(), 10);
+        for w in &words[90..] {
+            assert!(results.contains(w), "longest words survive the cap");
+        }
     }
 
     #[test]
-    fn test_solver_repeats() {
-        let mut config = Config::new().with_letters("ab").with_present("a");
-        config.repeats = Some(1);
-        config.minimal_word_length = Some(2);
+    fn test_iter_solutions_matches_solve() {
+        let mut config = Config::new().with_letters("Walrus").with_present("l");
+        config.case_sensitive = Some(true);
+        config.repeats = Some(RepeatPolicy::Max(2));
+        config.maximal_word_length = Some(5);
 
         let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["aa", "ab"]);
+        let dict = Dictionary::from_words(&[
+            "awls", "laws", "slaw", "wall", "walls", "walrus", "lull", "lulls", "rural",
+        ]);
 
-        let results = solver.solve(&dict).expect("Solver failed");
+        let solved = solver.solve(&dict).expect("Solver failed");
+        let iterated: HashSet<String> = solver.iter_solutions(&dict).collect();
 
-        assert!(results.contains("ab"), "Result should contain 'ab'");
-        assert!(
-            !results.contains("aa"),
-            "Result should NOT contain 'aa' due to repeat limit"
-        );
+        assert!(!solved.is_empty());
+        assert_eq!(iterated, solved);
     }
 
-    // --- Case-sensitive mode tests ---
+    #[test]
+    fn test_iter_solutions_invalid_config_is_empty() {
+        let solver = Solver::new(Config::new());
+        let dict = Dictionary::from_words(&["abcd"]);
+        assert_eq!(solver.iter_solutions(&dict).count(), 0);
+    }
 
     #[test]
-    fn test_solver_case_insensitive_default() {
-        // Uppercase input should be normalized when case_sensitive is off (default)
-        let config = Config::new().with_letters("ABCDEFG").with_present("A");
+    fn test_solver_repeats_once_is_isogram() {
+        let mut config = Config::new().with_letters("abcde").with_present("a");
+        config.repeats = Some(RepeatPolicy::Once);
 
         let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["fade", "faced", "bad"]);
+        let dict = Dictionary::from_words(&["bead", "abbe", "dace", "deed"]);
 
         let results = solver.solve(&dict).expect("Solver failed");
 
-        assert!(
-            results.contains("fade"),
-            "uppercase input should be normalized"
-        );
-        assert!(results.contains("faced"));
+        assert!(results.contains("bead"));
+        assert!(results.contains("dace"));
+        assert!(!results.contains("abbe"), "b used twice");
     }
 
     #[test]
-    fn test_solver_case_sensitive_start_only() {
-        // 'W' uppercase means 'w' can only appear at position 0
-        // 'a', 'r', 'e' are lowercase, can appear anywhere
-        let mut config = Config::new().with_letters("Ware").with_present("a");
-        config.case_sensitive = Some(true);
-        config.minimal_word_length = Some(3);
+    fn test_solver_repeats_zero_means_once() {
+        let config: Config =
+            serde_json::from_str(r#"{"letters":"abcde","present":"a","repeats":0}"#).unwrap();
+        assert_eq!(config.repeats, Some(RepeatPolicy::Once));
 
         let solver = Solver::new(config);
-        // "war" starts with w — OK
-        // "raw" has w not at start — should be excluded
-        // "ware" starts with w — OK
-        // "area" has no w — OK (w is not required)
-        let dict = Dictionary::from_words(&["war", "raw", "ware", "area", "aw"]);
+        let dict = Dictionary::from_words(&["bead", "abbe"]);
 
         let results = solver.solve(&dict).expect("Solver failed");
 
-        assert!(results.contains("war"), "w at position 0 is allowed");
-        assert!(!results.contains("raw"), "w at position > 0 is not allowed");
-        assert!(results.contains("ware"), "w at position 0 is allowed");
         assert!(
-            results.contains("area"),
-            "no w needed, all letters are anywhere-letters"
+            results.contains("bead"),
+            "repeats 0 must not reject everything"
         );
+        assert!(!results.contains("abbe"));
     }
 
     #[test]
-    fn test_solver_case_sensitive_required_start() {
-        // Uppercase in present means that letter must be at position 0
-        let mut config = Config::new().with_letters("Ware").with_present("W");
-        config.case_sensitive = Some(true);
-        config.minimal_word_length = Some(3);
+    fn test_solver_min_distinct_letters() {
+        let mut config = Config::new().with_letters("abcdefg").with_present("a");
+        config.min_distinct_letters = Some(5);
 
         let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["war", "raw", "ware", "area", "era"]);
+        let dict = Dictionary::from_words(&["faced", "decaf", "cabbage", "dada", "badge"]);
 
         let results = solver.solve(&dict).expect("Solver failed");
 
+        assert!(results.contains("faced"), "5 distinct letters");
         assert!(
-            results.contains("war"),
-            "starts with w, w is required start"
+            results.contains("cabbage"),
+            "5 distinct letters despite repeats"
         );
-        assert!(!results.contains("raw"), "w not at start");
-        assert!(results.contains("ware"), "starts with w");
-        assert!(!results.contains("area"), "does not start with w");
-        assert!(!results.contains("era"), "does not start with w");
+        assert!(results.contains("badge"));
+        assert!(!results.contains("dada"), "only 2 distinct letters");
     }
 
     #[test]
-    fn test_solver_case_sensitive_both_cases() {
-        // Both 'W' (start-only) and 'w' (anywhere) in letters
-        let mut config = Config::new().with_letters("Wware").with_present("a");
+    fn test_solver_min_distinct_letters_case_sensitive() {
+        // 'W' and 'w' share a base letter and count as one distinct letter
+        let mut config = Config::new().with_letters("Wwal").with_present("a");
         config.case_sensitive = Some(true);
-        config.minimal_word_length = Some(3);
+        config.min_distinct_letters = Some(3);
 
         let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["war", "raw", "ware", "awe"]);
+        let dict = Dictionary::from_words(&["wall", "walla", "awaw"]);
 
         let results = solver.solve(&dict).expect("Solver failed");
 
-        // Both 'W' (start-only) and 'w' (anywhere) contribute to allowed.
-        // 'w' (lowercase) is in anywhere, so w can appear at any position.
-        assert!(results.contains("war"));
-        assert!(results.contains("raw"), "lowercase w allows w anywhere");
-        assert!(results.contains("ware"));
-        assert!(results.contains("awe"), "lowercase w allows w anywhere");
+        assert!(results.contains("wall"));
+        assert!(!results.contains("awaw"), "only w and a");
     }
 
     #[test]
-    fn test_solver_case_sensitive_walrus_scenario() {
-        let mut config = Config::new().with_letters("Walrus").with_present("Wl");
-        config.case_sensitive = Some(true);
-        config.minimal_word_length = Some(4);
+    fn test_solver_preserve_case() {
+        let source = "Apple\nleap\nPale\n";
+        let dict = Dictionary::from_reader(std::io::Cursor::new(source)).unwrap();
+
+        let mut config = Config::new().with_letters("aple").with_present("a");
+        config.preserve_case = Some(true);
+        let results = Solver::new(config.clone()).solve(&dict).unwrap();
+
+        assert!(results.contains("Apple"));
+        assert!(results.contains("Pale"));
+        assert!(results.contains("leap"));
+
+        config.preserve_case = None;
+        let results = Solver::new(config).solve(&dict).unwrap();
+        assert!(results.contains("apple"), "lowercase by default");
+    }
+
+    #[test]
+    fn test_iter_solutions_preserve_case() {
+        let dict = Dictionary::from_reader(std::io::Cursor::new("Apple\n")).unwrap();
+        let mut config = Config::new().with_letters("aple").with_present("a");
+        config.preserve_case = Some(true);
 
         let solver = Solver::new(config);
-        let dict =
-            Dictionary::from_words(&["awls", "laws", "slaw", "wall", "walls", "walrus", "lure"]);
+        let words: Vec<String> = solver.iter_solutions(&dict).collect();
+        assert_eq!(words, vec!["Apple"]);
+    }
 
-        let results = solver.solve(&dict).expect("Solver failed");
+    #[test]
+    fn test_solver_allow_punctuation() {
+        let source = "well-being\nbeing\nwell\n";
+        let options = crate::dictionary::LoadOptions {
+            allow_punctuation: true,
+            ..Default::default()
+        };
+        let dict =
+            Dictionary::from_reader_with_options(std::io::Cursor::new(source), options).unwrap();
 
-        assert!(!results.contains("awls"), "awls does not start with w");
-        assert!(!results.contains("laws"), "laws does not start with w");
-        assert!(!results.contains("slaw"), "slaw does not start with w");
-        assert!(
-            !results.contains("lure"),
-            "lure does not contain l... wait it does, but no w start"
-        );
-        assert!(
-            results.contains("wall"),
-            "wall starts with w and contains l"
-        );
+        let mut config = Config::new().with_letters("welbing").with_present("w");
+        config.allow_punctuation = Some(true);
+        let results = Solver::new(config.clone()).solve(&dict).unwrap();
         assert!(
-            results.contains("walls"),
-            "walls starts with w and contains l"
+            results.contains("well-being"),
+            "connectors need not be available"
         );
+        assert!(results.contains("well"));
+
+        config.allow_punctuation = None;
+        let results = Solver::new(config).solve(&dict).unwrap();
         assert!(
-            results.contains("walrus"),
-            "walrus starts with w and contains l"
+            !results.contains("well-being"),
+            "connectors rejected when disabled"
         );
     }
 
     #[test]
-    fn test_solver_case_sensitive_all_lowercase_no_constraint() {
-        // When case_sensitive=true but all letters are lowercase, no positional constraint applies
-        let mut config = Config::new().with_letters("walrus").with_present("wl");
-        config.case_sensitive = Some(true);
-        config.minimal_word_length = Some(4);
+    fn test_word_is_pangram() {
+        let config = Config::new().with_letters("aelp").with_present("a");
+        let solver = Solver::new(config);
+        assert!(solver.word_is_pangram("apple"));
+        assert!(solver.word_is_pangram("PLEA"));
+        assert!(!solver.word_is_pangram("pal"));
+    }
 
+    fn sorted_by(order: SortOrder) -> Vec<String> {
+        let mut config = Config::new().with_letters("aelpt").with_present("a");
+        config.sort_order = Some(order);
         let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["awls", "laws", "wall", "walrus"]);
+        let mut words: Vec<String> = ["plate", "leap", "apple", "pale", "palette", "tale"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        solver.sort_words(&mut words);
+        words
+    }
 
-        let results = solver.solve(&dict).expect("Solver failed");
+    #[test]
+    fn test_sort_words_alpha() {
+        assert_eq!(
+            sorted_by(SortOrder::Alpha),
+            vec!["apple", "leap", "pale", "palette", "plate", "tale"]
+        );
+    }
 
-        assert!(
-            results.contains("awls"),
-            "all lowercase: w is an anywhere letter, no start constraint"
+    #[test]
+    fn test_sort_words_length() {
+        assert_eq!(
+            sorted_by(SortOrder::Length),
+            vec!["leap", "pale", "tale", "apple", "plate", "palette"]
         );
-        assert!(
-            results.contains("laws"),
-            "all lowercase: w is an anywhere letter"
+    }
+
+    #[test]
+    fn test_sort_words_length_desc() {
+        assert_eq!(
+            sorted_by(SortOrder::LengthDesc),
+            vec!["palette", "apple", "plate", "leap", "pale", "tale"]
         );
-        assert!(results.contains("wall"));
-        assert!(results.contains("walrus"));
     }
 
     #[test]
-    fn test_solver_case_sensitive_multiple_uppercase_required_error() {
-        let mut config = Config::new().with_letters("ABcde").with_present("AB");
-        config.case_sensitive = Some(true);
+    fn test_sort_words_score() {
+        // palette: 7 + 7 (pangram) = 14, plate: 5 + 7 = 12, apple: 5, four-letter words: 1
+        assert_eq!(
+            sorted_by(SortOrder::Score),
+            vec!["palette", "plate", "apple", "leap", "pale", "tale"]
+        );
+    }
 
-        let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["abcd"]);
+    #[test]
+    fn test_solve_sorted_is_stable_across_runs() {
+        let words = ["plea", "apple", "leap", "pale", "peal", "lapel", "appeal"];
+        let config = Config::new().with_letters("aelp").with_present("a");
+        let expected = Solver::new(config.clone())
+            .solve_sorted(&Dictionary::from_words(&words))
+            .unwrap();
+        assert_eq!(
+            expected,
+            vec!["appeal", "apple", "lapel", "leap", "pale", "peal", "plea"]
+        );
 
-        let result = solver.solve(&dict);
-        assert!(
-            result.is_err(),
-            "multiple uppercase required letters should error"
+        // Each dictionary's trie hashes differently, so only sorting can
+        // make repeated runs agree.
+        for rotation in 0..words.len() {
+            let mut shuffled = words;
+            shuffled.rotate_left(rotation);
+            let dict = Dictionary::from_words(&shuffled);
+            assert_eq!(
+                Solver::new(config.clone()).solve_sorted(&dict).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_solve_sorted_by_frequency() {
+        let source = "leap\t50\napple\t900\npale\nplate\t50\ntale\t3\n";
+        let dict = Dictionary::from_reader(std::io::Cursor::new(source)).unwrap();
+        let mut config = Config::new().with_letters("aelpt").with_present("a");
+        config.sort_order = Some(SortOrder::Frequency);
+
+        let words = Solver::new(config.clone()).solve_sorted(&dict).unwrap();
+
+        // Ties (leap, plate) break alphabetically; unweighted "pale" sinks
+        assert_eq!(words, vec!["apple", "leap", "plate", "tale", "pale"]);
+
+        let mut words = words;
+        Solver::new(config).sort_words(&mut words);
+        assert_eq!(
+            words,
+            vec!["apple", "leap", "pale", "plate", "tale"],
+            "without a dictionary, frequency falls back to alphabetical"
         );
-        let err_msg = result.unwrap_err().to_string();
-        assert!(err_msg.contains("At most one uppercase"));
+    }
+
+    fn trie_searches() -> usize {
+        TRIE_SEARCHES.with(|searches| searches.get())
     }
 
     #[test]
-    fn test_solver_no_required_letters() {
-        let mut config = Config::new().with_letters("ab");
-        config.minimal_word_length = Some(1);
-        let solver = Solver::new(config);
-        let dict = Dictionary::from_words(&["a", "ab", "ba", "b", "abc", "ca"]);
-        let result = solver.solve(&dict).unwrap();
-        // All words using only a and b should match
-        assert!(result.contains("a"));
-        assert!(result.contains("ab"));
-        assert!(result.contains("ba"));
-        assert!(result.contains("b"));
-        // Words with letters outside available set should not match
-        assert!(!result.contains("abc"));
-        assert!(!result.contains("ca"));
+    fn test_caching_solver_searches_once_per_puzzle() {
+        let dict = Dictionary::from_words(&["apple", "leap", "pale", "peel"]);
+        let cache = CachingSolver::new();
+        let config = Config::new().with_letters("aelp").with_present("a");
+        let expected = Solver::new(config.clone()).solve(&dict).unwrap();
+
+        let before = trie_searches();
+        let first = cache.solve(&config, &dict).unwrap();
+        let second = cache.solve(&config, &dict).unwrap();
+        assert_eq!(trie_searches() - before, 1, "second solve is a cache hit");
+        assert_eq!(first, expected);
+        assert_eq!(second, expected);
+
+        // Output-only fields share the entry; result-changing ones do not
+        let mut reordered = config.clone();
+        reordered.sort_order = Some(SortOrder::Length);
+        reordered.output = Some("words.txt".to_string());
+        cache.solve(&reordered, &dict).unwrap();
+        assert_eq!(trie_searches() - before, 1);
+        let mut longer = config.clone();
+        longer.minimal_word_length = Some(5);
+        assert_eq!(
+            cache.solve(&longer, &dict).unwrap(),
+            HashSet::from(["apple".to_string()])
+        );
+        assert_eq!(trie_searches() - before, 2);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_caching_solver_invalidates_on_dictionary_change() {
+        let mut dict = Dictionary::from_words(&["apple", "leap"]);
+        let cache = CachingSolver::new();
+        let config = Config::new().with_letters("aelp").with_present("a");
+
+        assert_eq!(cache.solve(&config, &dict).unwrap().len(), 2);
+        dict.insert_word("pale");
+        assert_eq!(cache.solve(&config, &dict).unwrap().len(), 3);
+
+        let other = Dictionary::from_words(&["plea"]);
+        assert_eq!(
+            cache.solve(&config, &other).unwrap(),
+            HashSet::from(["plea".to_string()])
+        );
+        assert_eq!(cache.len(), 1, "entries for the old dictionary are dropped");
+    }
+
+    #[test]
+    fn test_caching_solver_sorted_and_capacity() {
+        let dict = Dictionary::from_words(&["apple", "leap", "pale"]);
+        let cache = CachingSolver::with_capacity(1);
+        let mut config = Config::new().with_letters("aelp").with_present("a");
+        config.sort_order = Some(SortOrder::Length);
+        let deadline = Instant::now() + Duration::from_secs(5);
+
+        let words = cache
+            .solve_sorted_with_deadline(&config, &dict, deadline)
+            .unwrap();
+        assert_eq!(
+            words,
+            Solver::new(config.clone()).solve_sorted(&dict).unwrap()
+        );
+
+        let mut other = config.clone();
+        other.present = Some("p".to_string());
+        cache.solve(&other, &dict).unwrap();
+        assert_eq!(cache.len(), 1, "full cache is cleared before inserting");
+    }
+
+    #[test]
+    fn test_caching_solver_does_not_cache_errors() {
+        let dict = Dictionary::from_words(&["apple"]);
+        let cache = CachingSolver::new();
+        assert!(cache.solve(&Config::new(), &dict).is_err());
+        assert!(cache.is_empty());
     }
 }
@@ -1,21 +1,54 @@
 //! External dictionary validation and lookup.
 
+use crate::dictionary::Dictionary;
 use crate::error::SbsError;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
-/// HTTP request timeout for validator API calls.
-const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+/// Default HTTP request timeout for validator API calls.
+pub const DEFAULT_HTTP_TIMEOUT: Duration = Duration::from_secs(10);
 
-/// Delay between consecutive API calls to avoid rate limiting.
+/// Longest HTTP request timeout a validator accepts.
+const MAX_HTTP_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// `User-Agent` sent with every validator request; some APIs reject reqwest's default.
+const USER_AGENT: &str = concat!("spelling-bee-solver/", env!("CARGO_PKG_VERSION"));
+
+/// Default delay between consecutive API calls to avoid rate limiting.
 const THROTTLE_DELAY: Duration = Duration::from_millis(100);
 
+/// Default number of retries for transient HTTP failures.
+const DEFAULT_MAX_RETRIES: u32 = 2;
+
+/// Backoff before the first retry; doubled on every further attempt.
+const RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Default number of definitions collected per word.
+const DEFAULT_MAX_DEFINITIONS: usize = 1;
+
+/// Query parameters whose values are credentials, hidden by [`redact_url`].
+const SECRET_PARAMS: [&str; 2] = ["key=", "api_key="];
+
+/// Placeholder used when a source confirms a word but gives no definition.
+const NO_DEFINITION: &str = "No definition available";
+
 /// A validated word entry with definition and reference URL.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WordEntry {
     pub word: String,
     pub definition: String,
     pub url: String,
+    /// All collected definitions, most relevant first; `definition` is the first.
+    #[serde(default)]
+    pub definitions: Vec<String>,
+    /// Part of speech of the first sense, e.g. "noun", when the source reports it.
+    #[serde(default)]
+    pub part_of_speech: Option<String>,
 }
 
 /// Summary of validation results.
@@ -24,16 +57,45 @@ pub struct ValidationSummary {
     pub candidates: usize,
     pub validated: usize,
     pub entries: Vec<WordEntry>,
+    /// Set when a deadline stopped validation before every candidate was checked.
+    #[serde(default)]
+    pub truncated: bool,
+    /// Candidates that could not be checked because the lookup failed (e.g.
+    /// rate limiting or network errors), as opposed to confirmed absent.
+    #[serde(default)]
+    pub unavailable: usize,
+}
+
+/// Result of checking a single word, see [`Validator::check`].
+#[derive(Debug, Clone)]
+pub enum LookupOutcome {
+    Found(WordEntry),
+    /// The source answered and does not know the word.
+    NotFound,
+    /// The source could not be consulted; the message says why.
+    Unavailable(String),
+}
+
+impl From<Result<Option<WordEntry>, SbsError>> for LookupOutcome {
+    fn from(result: Result<Option<WordEntry>, SbsError>) -> Self {
+        match result {
+            Ok(Some(entry)) => LookupOutcome::Found(entry),
+            Ok(None) => LookupOutcome::NotFound,
+            Err(e) => LookupOutcome::Unavailable(e.to_string()),
+        }
+    }
 }
 
 /// Supported external dictionary validators.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, schemars::JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum ValidatorKind {
     FreeDictionary,
     MerriamWebster,
     Wordnik,
     Custom,
+    Offline,
+    Datamuse,
 }
 
 impl ValidatorKind {
@@ -43,40 +105,184 @@ impl ValidatorKind {
             ValidatorKind::MerriamWebster => "Merriam-Webster",
             ValidatorKind::Wordnik => "Wordnik",
             ValidatorKind::Custom => "Custom",
+            ValidatorKind::Offline => "Offline",
+            ValidatorKind::Datamuse => "Datamuse",
         }
     }
 }
 
+/// Parses the canonical kebab-case names case-insensitively, plus a few
+/// common aliases (`mw`, `free`, `dictapi`, `wn`, and unhyphenated spellings).
 impl std::str::FromStr for ValidatorKind {
     type Err = SbsError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "free-dictionary" => Ok(ValidatorKind::FreeDictionary),
-            "merriam-webster" => Ok(ValidatorKind::MerriamWebster),
-            "wordnik" => Ok(ValidatorKind::Wordnik),
+        match s.trim().to_ascii_lowercase().as_str() {
+            "free-dictionary" | "freedictionary" | "free" | "dictapi" => {
+                Ok(ValidatorKind::FreeDictionary)
+            }
+            "merriam-webster" | "merriamwebster" | "mw" => Ok(ValidatorKind::MerriamWebster),
+            "wordnik" | "wn" => Ok(ValidatorKind::Wordnik),
             "custom" => Ok(ValidatorKind::Custom),
+            "offline" => Ok(ValidatorKind::Offline),
+            "datamuse" => Ok(ValidatorKind::Datamuse),
             _ => Err(SbsError::ValidationError(format!(
-                "Unknown validator: '{}'. Valid options: free-dictionary, merriam-webster, wordnik, custom",
+                "Unknown validator: '{}'. Valid options: free-dictionary, merriam-webster, wordnik, custom, offline, datamuse",
                 s
             ))),
         }
     }
 }
 
-/// Build a shared HTTP client with timeout.
-fn http_client() -> Result<reqwest::blocking::Client, SbsError> {
+/// Build a shared HTTP client with the given request timeout.
+///
+/// A zero timeout or one above five minutes is rejected.
+pub(crate) fn http_client(timeout: Duration) -> Result<reqwest::blocking::Client, SbsError> {
+    if timeout.is_zero() || timeout > MAX_HTTP_TIMEOUT {
+        return Err(SbsError::ValidationError(format!(
+            "Validator timeout must be between 1 and {} seconds, got {:?}",
+            MAX_HTTP_TIMEOUT.as_secs(),
+            timeout
+        )));
+    }
     reqwest::blocking::Client::builder()
-        .timeout(HTTP_TIMEOUT)
+        .timeout(timeout)
+        .user_agent(USER_AGENT)
         .build()
         .map_err(|e| SbsError::ValidationError(format!("Failed to create HTTP client: {}", e)))
 }
 
+/// Parse a request header, rejecting names or values HTTP cannot carry.
+fn parse_header(name: &str, value: &str) -> Result<(HeaderName, HeaderValue), SbsError> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        SbsError::ValidationError(format!("Invalid header '{}': {}", name, e))
+    };
+    let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| invalid(&e))?;
+    let mut value = HeaderValue::from_str(value).map_err(|e| invalid(&e))?;
+    value.set_sensitive(true);
+    Ok((name, value))
+}
+
+/// Replace API key query values in `text` (a URL, or a message quoting one)
+/// with `***`, so keys never reach error messages or logs.
+fn redact_url(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(['?', '&']) {
+        let (head, tail) = rest.split_at(start + 1);
+        redacted.push_str(head);
+        rest = tail;
+        if let Some(param) = SECRET_PARAMS.iter().find(|p| rest.starts_with(*p)) {
+            let end = rest
+                .find(|c: char| c == '&' || c == ')' || c.is_whitespace())
+                .unwrap_or(rest.len());
+            redacted.push_str(param);
+            redacted.push_str("***");
+            rest = &rest[end..];
+        }
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+/// Wrap a reqwest error, which quotes the request URL, with its keys redacted.
+fn http_error(context: &str, e: reqwest::Error) -> SbsError {
+    SbsError::ValidationError(format!("{}: {}", context, redact_url(&e.to_string())))
+}
+
+/// The leading definition, or a placeholder when a source has none.
+fn first_definition(definitions: &[String]) -> String {
+    definitions
+        .first()
+        .cloned()
+        .unwrap_or_else(|| NO_DEFINITION.to_string())
+}
+
+/// Whether an HTTP status signals a transient failure worth retrying.
+fn is_transient_status(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 429 | 500 | 502 | 503 | 504)
+}
+
+/// Error for a non-success HTTP status; auth failures get an actionable message.
+fn status_error(status: reqwest::StatusCode) -> SbsError {
+    match status.as_u16() {
+        401 | 403 => SbsError::ValidationError(format!(
+            "Invalid or missing API key (API returned status {})",
+            status
+        )),
+        _ => SbsError::ValidationError(format!("API returned status {}", status)),
+    }
+}
+
+/// GET `url`, retrying transient failures with exponential backoff.
+///
+/// Rate limiting (429), gateway/server errors (500, 502-504), connection
+/// failures and timeouts are retried up to `max_retries` times. Any other
+/// response, including 404, is returned as-is. `headers` are sent with every
+/// attempt.
+fn get_with_retry(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    headers: &HeaderMap,
+    max_retries: u32,
+) -> Result<reqwest::blocking::Response, SbsError> {
+    let mut attempt = 0;
+    loop {
+        let result = client.get(url).headers(headers.clone()).send();
+        let transient = match &result {
+            Ok(response) => is_transient_status(response.status()),
+            Err(e) => e.is_connect() || e.is_timeout(),
+        };
+        if !transient || attempt >= max_retries {
+            return result.map_err(|e| http_error("HTTP error", e));
+        }
+        let delay = RETRY_BACKOFF * 2u32.pow(attempt);
+        log::debug!(
+            "Transient failure for {}, retrying in {:?}",
+            redact_url(url),
+            delay
+        );
+        std::thread::sleep(delay);
+        attempt += 1;
+    }
+}
+
+/// Word looked up by [`Validator::self_test`].
+pub const SELF_TEST_WORD: &str = "test";
+
 /// Trait for external dictionary validators.
 pub trait Validator: Send + Sync {
     fn name(&self) -> &str;
     fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError>;
 
+    /// Look up `word`, separating "not in the dictionary" from "could not check".
+    fn check(&self, word: &str) -> LookupOutcome {
+        self.lookup(word).into()
+    }
+
+    /// Confirm the source is reachable and configured (e.g. that its API key
+    /// is accepted) by looking up [`SELF_TEST_WORD`], which every source knows.
+    fn self_test(&self) -> Result<(), SbsError> {
+        match self.check(SELF_TEST_WORD) {
+            LookupOutcome::Found(_) => Ok(()),
+            LookupOutcome::NotFound => Err(SbsError::ValidationError(format!(
+                "{} answered but does not know '{}'; check the endpoint",
+                self.name(),
+                SELF_TEST_WORD
+            ))),
+            LookupOutcome::Unavailable(reason) => Err(SbsError::ValidationError(format!(
+                "{} is unavailable: {}",
+                self.name(),
+                reason
+            ))),
+        }
+    }
+
+    /// Delay inserted between consecutive lookups by `validate_words`.
+    fn throttle_delay(&self) -> Duration {
+        THROTTLE_DELAY
+    }
+
     /// Validate a list of words with throttling. Returns a summary with counts.
     fn validate_words(&self, words: &[String]) -> ValidationSummary {
         self.validate_words_with_progress(words, &|_, _| {})
@@ -87,101 +293,293 @@ pub trait Validator: Send + Sync {
         &self,
         words: &[String],
         on_progress: &dyn Fn(usize, usize),
+    ) -> ValidationSummary {
+        validate_serially(self, words, None, on_progress, &|_| {})
+    }
+
+    /// Like `validate_words_with_progress`, but also hands each confirmed
+    /// entry to `on_entry` as soon as its lookup succeeds.
+    fn validate_words_streaming(
+        &self,
+        words: &[String],
+        on_progress: &dyn Fn(usize, usize),
+        on_entry: &dyn Fn(&WordEntry),
+    ) -> ValidationSummary {
+        validate_serially(self, words, None, on_progress, on_entry)
+    }
+
+    /// Validate words serially until `deadline`, then return what was
+    /// confirmed so far with `truncated` set. A lookup already in flight at
+    /// the deadline is allowed to finish.
+    fn validate_words_until(&self, words: &[String], deadline: Instant) -> ValidationSummary {
+        validate_serially(self, words, Some(deadline), &|_, _| {}, &|_| {})
+    }
+
+    /// Validate a list of words with up to `concurrency` lookups in flight.
+    /// Entries keep the input order and counts match `validate_words`.
+    fn validate_words_concurrent(&self, words: &[String], concurrency: usize) -> ValidationSummary {
+        self.validate_words_concurrent_with_progress(words, concurrency, &|_, _| {})
+    }
+
+    /// Concurrent validation with a progress callback invoked as lookups
+    /// complete. Lookup starts are still spaced by `throttle_delay`, so the
+    /// overall request rate is the same as the serial path.
+    fn validate_words_concurrent_with_progress(
+        &self,
+        words: &[String],
+        concurrency: usize,
+        on_progress: &(dyn Fn(usize, usize) + Sync),
     ) -> ValidationSummary {
         let candidates = words.len();
-        let mut entries = Vec::new();
-        for (i, word) in words.iter().enumerate() {
-            if i > 0 {
-                std::thread::sleep(THROTTLE_DELAY);
-            }
-            match self.lookup(word) {
-                Ok(Some(entry)) => entries.push(entry),
-                Ok(None) => {}
-                Err(e) => {
-                    log::warn!("Validation error for '{}': {}", word, e);
-                }
+        let next_word = AtomicUsize::new(0);
+        let completed = AtomicUsize::new(0);
+        let unavailable = AtomicUsize::new(0);
+        let next_start = Mutex::new(Instant::now());
+        let results: Mutex<Vec<Option<WordEntry>>> = Mutex::new(vec![None; candidates]);
+
+        std::thread::scope(|scope| {
+            for _ in 0..concurrency.clamp(1, candidates.max(1)) {
+                scope.spawn(|| loop {
+                    let i = next_word.fetch_add(1, Ordering::SeqCst);
+                    let Some(word) = words.get(i) else {
+                        break;
+                    };
+                    let start = {
+                        let mut next = next_start.lock().unwrap_or_else(|e| e.into_inner());
+                        let start = (*next).max(Instant::now());
+                        *next = start + self.throttle_delay();
+                        start
+                    };
+                    std::thread::sleep(start.saturating_duration_since(Instant::now()));
+                    match self.check(word) {
+                        LookupOutcome::Found(entry) => {
+                            results.lock().unwrap_or_else(|e| e.into_inner())[i] = Some(entry);
+                        }
+                        LookupOutcome::NotFound => {}
+                        LookupOutcome::Unavailable(reason) => {
+                            log::warn!("Validation error for '{}': {}", word, reason);
+                            unavailable.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+                    on_progress(completed.fetch_add(1, Ordering::SeqCst) + 1, candidates);
+                });
             }
-            on_progress(i + 1, candidates);
-        }
+        });
+
+        let entries: Vec<WordEntry> = results
+            .into_inner()
+            .unwrap_or_else(|e| e.into_inner())
+            .into_iter()
+            .flatten()
+            .collect();
         let validated = entries.len();
         ValidationSummary {
             candidates,
             validated,
             entries,
+            truncated: false,
+            unavailable: unavailable.into_inner(),
+        }
+    }
+}
+
+/// The serial validation loop behind [`Validator::validate_words_with_progress`],
+/// [`Validator::validate_words_streaming`] and [`Validator::validate_words_until`].
+fn validate_serially<V: Validator + ?Sized>(
+    validator: &V,
+    words: &[String],
+    deadline: Option<Instant>,
+    on_progress: &dyn Fn(usize, usize),
+    on_entry: &dyn Fn(&WordEntry),
+) -> ValidationSummary {
+    let candidates = words.len();
+    let mut entries = Vec::new();
+    let mut truncated = false;
+    let mut unavailable = 0;
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            let pause = match deadline {
+                Some(deadline) => validator
+                    .throttle_delay()
+                    .min(deadline.saturating_duration_since(Instant::now())),
+                None => validator.throttle_delay(),
+            };
+            std::thread::sleep(pause);
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            truncated = true;
+            break;
+        }
+        match validator.check(word) {
+            LookupOutcome::Found(entry) => {
+                on_entry(&entry);
+                entries.push(entry);
+            }
+            LookupOutcome::NotFound => {}
+            LookupOutcome::Unavailable(reason) => {
+                log::warn!("Validation error for '{}': {}", word, reason);
+                unavailable += 1;
+            }
         }
+        on_progress(i + 1, candidates);
+    }
+    let validated = entries.len();
+    ValidationSummary {
+        candidates,
+        validated,
+        entries,
+        truncated,
+        unavailable,
+    }
+}
+
+/// HTTP client and request policy shared by the online validators, set
+/// through the [`HttpValidator`] builders.
+#[derive(Clone)]
+pub struct HttpSettings {
+    client: reqwest::blocking::Client,
+    throttle: Duration,
+    max_retries: u32,
+    max_definitions: usize,
+    headers: HeaderMap,
+}
+
+impl HttpSettings {
+    fn new() -> Result<Self, SbsError> {
+        Ok(Self {
+            client: http_client(DEFAULT_HTTP_TIMEOUT)?,
+            throttle: THROTTLE_DELAY,
+            max_retries: DEFAULT_MAX_RETRIES,
+            max_definitions: DEFAULT_MAX_DEFINITIONS,
+            headers: HeaderMap::new(),
+        })
+    }
+
+    /// GET `url` with the extra headers and retries, see [`get_with_retry`].
+    fn get(&self, url: &str) -> Result<reqwest::blocking::Response, SbsError> {
+        get_with_retry(&self.client, url, &self.headers, self.max_retries)
+    }
+}
+
+/// Builders for the [`HttpSettings`] of an online validator.
+pub trait HttpValidator: Sized {
+    fn http_settings(&mut self) -> &mut HttpSettings;
+
+    /// Override the delay between consecutive lookups.
+    fn with_throttle(mut self, throttle: Duration) -> Self {
+        self.http_settings().throttle = throttle;
+        self
+    }
+
+    /// Override how many times transient HTTP failures are retried.
+    fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.http_settings().max_retries = max_retries;
+        self
+    }
+
+    /// Collect up to `max_definitions` definitions per word (at least one).
+    fn with_max_definitions(mut self, max_definitions: usize) -> Self {
+        self.http_settings().max_definitions = max_definitions.max(1);
+        self
+    }
+
+    /// Override the HTTP request timeout (default 10 seconds).
+    fn with_timeout(mut self, timeout: Duration) -> Result<Self, SbsError> {
+        self.http_settings().client = http_client(timeout)?;
+        Ok(self)
+    }
+
+    /// Send an extra header with every request, e.g. `Authorization: Bearer ...`.
+    fn with_header(mut self, name: &str, value: &str) -> Result<Self, SbsError> {
+        let (name, value) = parse_header(name, value)?;
+        self.http_settings().headers.insert(name, value);
+        Ok(self)
     }
 }
 
 /// Free Dictionary API validator (no API key required).
 pub struct FreeDictionaryValidator {
     base_url: String,
-    client: reqwest::blocking::Client,
+    http: HttpSettings,
 }
 
 impl FreeDictionaryValidator {
     pub fn new() -> Result<Self, SbsError> {
         Ok(Self {
             base_url: "https://api.dictionaryapi.dev/api/v2/entries/en".to_string(),
-            client: http_client()?,
+            http: HttpSettings::new()?,
         })
     }
 
     pub fn with_base_url(base_url: &str) -> Result<Self, SbsError> {
         Ok(Self {
             base_url: base_url.to_string(),
-            client: http_client()?,
+            http: HttpSettings::new()?,
         })
     }
 }
 
+impl HttpValidator for FreeDictionaryValidator {
+    fn http_settings(&mut self) -> &mut HttpSettings {
+        &mut self.http
+    }
+}
+
 impl Validator for FreeDictionaryValidator {
     fn name(&self) -> &str {
         "Free Dictionary"
     }
 
+    fn throttle_delay(&self) -> Duration {
+        self.http.throttle
+    }
+
     fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError> {
         let url = format!("{}/{}", self.base_url, word);
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .map_err(|e| SbsError::ValidationError(format!("HTTP error: {}", e)))?;
+        let response = self.http.get(&url)?;
 
         if response.status() == 404 {
             return Ok(None);
         }
 
         if !response.status().is_success() {
-            return Err(SbsError::ValidationError(format!(
-                "API returned status {}",
-                response.status()
-            )));
+            return Err(status_error(response.status()));
         }
 
         let body: serde_json::Value = response
             .json()
-            .map_err(|e| SbsError::ValidationError(format!("JSON parse error: {}", e)))?;
+            .map_err(|e| http_error("JSON parse error", e))?;
 
-        let definition = body
+        let meanings = body
             .as_array()
             .and_then(|arr| arr.first())
             .and_then(|entry| entry.get("meanings"))
-            .and_then(|m| m.as_array())
-            .and_then(|arr| arr.first())
-            .and_then(|meaning| meaning.get("definitions"))
-            .and_then(|d| d.as_array())
+            .and_then(|m| m.as_array());
+
+        let definitions: Vec<String> = meanings
+            .into_iter()
+            .flatten()
+            .filter_map(|meaning| meaning.get("definitions").and_then(|d| d.as_array()))
+            .flatten()
+            .filter_map(|def| def.get("definition").and_then(|d| d.as_str()))
+            .take(self.http.max_definitions)
+            .map(str::to_string)
+            .collect();
+
+        let part_of_speech = meanings
             .and_then(|arr| arr.first())
-            .and_then(|def| def.get("definition"))
-            .and_then(|d| d.as_str())
-            .unwrap_or("No definition available")
-            .to_string();
+            .and_then(|meaning| meaning.get("partOfSpeech"))
+            .and_then(|p| p.as_str())
+            .map(str::to_string);
 
         let entry_url = format!("https://en.wiktionary.org/wiki/{}", word);
 
         Ok(Some(WordEntry {
             word: word.to_string(),
-            definition,
+            definition: first_definition(&definitions),
+            definitions,
             url: entry_url,
+            part_of_speech,
         }))
     }
 }
@@ -189,44 +587,47 @@ impl Validator for FreeDictionaryValidator {
 /// Merriam-Webster API validator (requires free API key).
 pub struct MerriamWebsterValidator {
     api_key: String,
-    client: reqwest::blocking::Client,
+    http: HttpSettings,
 }
 
 impl MerriamWebsterValidator {
     pub fn new(api_key: &str) -> Result<Self, SbsError> {
         Ok(Self {
             api_key: api_key.to_string(),
-            client: http_client()?,
+            http: HttpSettings::new()?,
         })
     }
 }
 
+impl HttpValidator for MerriamWebsterValidator {
+    fn http_settings(&mut self) -> &mut HttpSettings {
+        &mut self.http
+    }
+}
+
 impl Validator for MerriamWebsterValidator {
     fn name(&self) -> &str {
         "Merriam-Webster"
     }
 
+    fn throttle_delay(&self) -> Duration {
+        self.http.throttle
+    }
+
     fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError> {
         let url = format!(
             "https://dictionaryapi.com/api/v3/references/collegiate/json/{}?key={}",
             word, self.api_key
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .map_err(|e| SbsError::ValidationError(format!("HTTP error: {}", e)))?;
+        let response = self.http.get(&url)?;
 
         if !response.status().is_success() {
-            return Err(SbsError::ValidationError(format!(
-                "API returned status {}",
-                response.status()
-            )));
+            return Err(status_error(response.status()));
         }
 
         let body: serde_json::Value = response
             .json()
-            .map_err(|e| SbsError::ValidationError(format!("JSON parse error: {}", e)))?;
+            .map_err(|e| http_error("JSON parse error", e))?;
 
         // Merriam-Webster returns an array of strings (suggestions) if word not found,
         // or an array of objects if found.
@@ -243,36 +644,58 @@ impl Validator for MerriamWebsterValidator {
             return Ok(None);
         }
 
-        let definition = arr[0]
+        let definitions: Vec<String> = arr[0]
             .get("shortdef")
             .and_then(|sd| sd.as_array())
-            .and_then(|arr| arr.first())
-            .and_then(|d| d.as_str())
-            .unwrap_or("No definition available")
-            .to_string();
+            .into_iter()
+            .flatten()
+            .filter_map(|d| d.as_str())
+            .take(self.http.max_definitions)
+            .map(str::to_string)
+            .collect();
+
+        let part_of_speech = arr[0]
+            .get("fl")
+            .and_then(|fl| fl.as_str())
+            .map(str::to_string);
 
         let entry_url = format!("https://www.merriam-webster.com/dictionary/{}", word);
 
         Ok(Some(WordEntry {
             word: word.to_string(),
-            definition,
+            definition: first_definition(&definitions),
+            definitions,
             url: entry_url,
+            part_of_speech,
         }))
     }
 }
 
 /// Wordnik API validator (requires free API key).
 pub struct WordnikValidator {
-    api_key: String,
-    client: reqwest::blocking::Client,
+    base_url: String,
+    http: HttpSettings,
 }
 
 impl WordnikValidator {
+    /// The key travels in Wordnik's `api_key` header rather than the URL, so
+    /// it cannot leak through logged request URLs.
     pub fn new(api_key: &str) -> Result<Self, SbsError> {
-        Ok(Self {
-            api_key: api_key.to_string(),
-            client: http_client()?,
-        })
+        Self::with_base_url(api_key, "https://api.wordnik.com/v4/word.json")
+    }
+
+    pub fn with_base_url(api_key: &str, base_url: &str) -> Result<Self, SbsError> {
+        Self {
+            base_url: base_url.to_string(),
+            http: HttpSettings::new()?,
+        }
+        .with_header("api_key", api_key)
+    }
+}
+
+impl HttpValidator for WordnikValidator {
+    fn http_settings(&mut self) -> &mut HttpSettings {
+        &mut self.http
     }
 }
 
@@ -281,64 +704,165 @@ impl Validator for WordnikValidator {
         "Wordnik"
     }
 
+    fn throttle_delay(&self) -> Duration {
+        self.http.throttle
+    }
+
     fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError> {
         let url = format!(
-            "https://api.wordnik.com/v4/word.json/{}/definitions?limit=1&api_key={}",
-            word, self.api_key
+            "{}/{}/definitions?limit={}",
+            self.base_url, word, self.http.max_definitions
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .map_err(|e| SbsError::ValidationError(format!("HTTP error: {}", e)))?;
+        let response = self.http.get(&url)?;
 
         if response.status() == 404 {
             return Ok(None);
         }
 
         if !response.status().is_success() {
-            return Err(SbsError::ValidationError(format!(
-                "API returned status {}",
-                response.status()
-            )));
+            return Err(status_error(response.status()));
         }
 
         let body: serde_json::Value = response
             .json()
-            .map_err(|e| SbsError::ValidationError(format!("JSON parse error: {}", e)))?;
+            .map_err(|e| http_error("JSON parse error", e))?;
 
         let arr = match body.as_array() {
             Some(a) if !a.is_empty() => a,
             _ => return Ok(None),
         };
 
-        let definition = arr[0]
-            .get("text")
-            .and_then(|t| t.as_str())
-            .unwrap_or("No definition available")
-            .to_string();
+        let definitions: Vec<String> = arr
+            .iter()
+            .filter_map(|d| d.get("text").and_then(|t| t.as_str()))
+            .take(self.http.max_definitions)
+            .map(str::to_string)
+            .collect();
+
+        let part_of_speech = arr[0]
+            .get("partOfSpeech")
+            .and_then(|p| p.as_str())
+            .map(str::to_string);
 
         let entry_url = format!("https://www.wordnik.com/words/{}", word);
 
         Ok(Some(WordEntry {
             word: word.to_string(),
-            definition,
+            definition: first_definition(&definitions),
+            definitions,
             url: entry_url,
+            part_of_speech,
         }))
     }
 }
 
+/// Datamuse API validator (no API key required).
+pub struct DatamuseValidator {
+    base_url: String,
+    http: HttpSettings,
+}
+
+impl DatamuseValidator {
+    pub fn new() -> Result<Self, SbsError> {
+        Self::with_base_url("https://api.datamuse.com/words")
+    }
+
+    pub fn with_base_url(base_url: &str) -> Result<Self, SbsError> {
+        Ok(Self {
+            base_url: base_url.to_string(),
+            http: HttpSettings::new()?,
+        })
+    }
+}
+
+/// Extract up to `max` definitions and the first sense's part of speech for
+/// `word` from a Datamuse `md=d` response.
+///
+/// Datamuse returns spelling-similar words too, so only an exact match counts.
+/// Definitions are formatted as `"<pos>\t<text>"`.
+fn parse_datamuse_definitions(
+    word: &str,
+    body: &serde_json::Value,
+    max: usize,
+) -> Option<(Vec<String>, Option<String>)> {
+    let entry = body
+        .as_array()?
+        .iter()
+        .find(|e| e.get("word").and_then(|w| w.as_str()) == Some(word))?;
+    let defs: Vec<(&str, &str)> = entry
+        .get("defs")?
+        .as_array()?
+        .iter()
+        .filter_map(|d| d.as_str())
+        .take(max)
+        .map(|d| d.split_once('\t').unwrap_or(("", d)))
+        .collect();
+    let (tag, _) = defs.first()?;
+    let part_of_speech = match *tag {
+        "n" => Some("noun"),
+        "v" => Some("verb"),
+        "adj" => Some("adjective"),
+        "adv" => Some("adverb"),
+        _ => None,
+    };
+    Some((
+        defs.iter().map(|(_, text)| text.to_string()).collect(),
+        part_of_speech.map(str::to_string),
+    ))
+}
+
+impl HttpValidator for DatamuseValidator {
+    fn http_settings(&mut self) -> &mut HttpSettings {
+        &mut self.http
+    }
+}
+
+impl Validator for DatamuseValidator {
+    fn name(&self) -> &str {
+        "Datamuse"
+    }
+
+    fn throttle_delay(&self) -> Duration {
+        self.http.throttle
+    }
+
+    fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError> {
+        let url = format!("{}?sp={}&md=d", self.base_url, word);
+        let response = self.http.get(&url)?;
+
+        if !response.status().is_success() {
+            return Err(status_error(response.status()));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .map_err(|e| http_error("JSON parse error", e))?;
+
+        Ok(
+            parse_datamuse_definitions(&word.to_lowercase(), &body, self.http.max_definitions).map(
+                |(definitions, part_of_speech)| WordEntry {
+                    word: word.to_string(),
+                    definition: first_definition(&definitions),
+                    definitions,
+                    url,
+                    part_of_speech,
+                },
+            ),
+        )
+    }
+}
+
 /// Custom URL validator (assumes Free Dictionary API-compatible JSON format).
 pub struct CustomValidator {
     base_url: String,
-    client: reqwest::blocking::Client,
+    http: HttpSettings,
 }
 
 impl CustomValidator {
     pub fn new(base_url: &str) -> Result<Self, SbsError> {
         Ok(Self {
             base_url: base_url.trim_end_matches('/').to_string(),
-            client: http_client()?,
+            http: HttpSettings::new()?,
         })
     }
 
@@ -346,10 +870,12 @@ impl CustomValidator {
     pub fn probe(&self) -> Result<bool, SbsError> {
         let test_url = format!("{}/test", self.base_url);
         let response = self
+            .http
             .client
             .get(&test_url)
+            .headers(self.http.headers.clone())
             .send()
-            .map_err(|e| SbsError::ValidationError(format!("Probe failed: {}", e)))?;
+            .map_err(|e| http_error("Probe failed", e))?;
 
         if !response.status().is_success() {
             return Ok(false);
@@ -370,88 +896,362 @@ impl CustomValidator {
     }
 }
 
+impl HttpValidator for CustomValidator {
+    fn http_settings(&mut self) -> &mut HttpSettings {
+        &mut self.http
+    }
+}
+
 impl Validator for CustomValidator {
     fn name(&self) -> &str {
         "Custom"
     }
 
+    fn throttle_delay(&self) -> Duration {
+        self.http.throttle
+    }
+
     fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError> {
         // Reuse Free Dictionary parsing logic since custom validators are expected
-        // to be API-compatible.
-        let inner = FreeDictionaryValidator::with_base_url(&self.base_url)?;
+        // to be API-compatible. The client is shared so its timeout carries over.
+        let inner = FreeDictionaryValidator {
+            base_url: self.base_url.clone(),
+            http: self.http.clone(),
+        };
         inner.lookup(word)
     }
 }
 
-/// Create a boxed validator from a kind, API key, and optional custom URL.
-pub fn create_validator(
-    kind: &ValidatorKind,
-    api_key: Option<&str>,
-    custom_url: Option<&str>,
-) -> Result<Box<dyn Validator>, SbsError> {
-    match kind {
-        ValidatorKind::FreeDictionary => Ok(Box::new(FreeDictionaryValidator::new()?)),
-        ValidatorKind::MerriamWebster => {
-            let key = api_key.ok_or_else(|| {
-                SbsError::ValidationError(
-                    "Merriam-Webster requires an API key (--api-key)".to_string(),
-                )
-            })?;
-            Ok(Box::new(MerriamWebsterValidator::new(key)?))
-        }
-        ValidatorKind::Wordnik => {
-            let key = api_key.ok_or_else(|| {
-                SbsError::ValidationError("Wordnik requires an API key (--api-key)".to_string())
-            })?;
-            Ok(Box::new(WordnikValidator::new(key)?))
-        }
-        ValidatorKind::Custom => {
-            let url = custom_url.ok_or_else(|| {
-                SbsError::ValidationError(
-                    "Custom validator requires a URL (--validator-url)".to_string(),
-                )
-            })?;
-            let validator = CustomValidator::new(url)?;
-            if !validator.probe()? {
-                return Err(SbsError::ValidationError(format!(
-                    "Custom URL '{}' does not appear to be a compatible dictionary API. \
-                     Expected Free Dictionary API-compatible JSON format.",
-                    url
-                )));
-            }
-            Ok(Box::new(validator))
+/// Placeholder definition reported by [`OfflineValidator`].
+const OFFLINE_DEFINITION: &str = "(offline: present in wordlist)";
+
+/// Offline validator that checks membership in a local curated word list.
+///
+/// With the `bloom` feature, most misses are rejected by the dictionary's
+/// bloom filter without walking the trie.
+pub struct OfflineValidator {
+    dictionary: Dictionary,
+    throttle: Duration,
+}
+
+impl OfflineValidator {
+    /// Local lookups need no rate limiting, so the throttle defaults to zero.
+    pub fn new(dictionary: Dictionary) -> Self {
+        Self {
+            dictionary,
+            throttle: Duration::ZERO,
         }
     }
+
+    pub fn from_file(path: &str) -> Result<Self, SbsError> {
+        Ok(Self::new(Dictionary::from_file(path)?))
+    }
+
+    /// Override the delay between consecutive lookups.
+    pub fn with_throttle(mut self, throttle: Duration) -> Self {
+        self.throttle = throttle;
+        self
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Validator for OfflineValidator {
+    fn name(&self) -> &str {
+        "Offline"
+    }
 
-    #[test]
-    fn test_validator_kind_from_str() {
-        assert_eq!(
-            "free-dictionary".parse::<ValidatorKind>().unwrap(),
-            ValidatorKind::FreeDictionary
-        );
-        assert_eq!(
-            "merriam-webster".parse::<ValidatorKind>().unwrap(),
-            ValidatorKind::MerriamWebster
-        );
-        assert_eq!(
-            "wordnik".parse::<ValidatorKind>().unwrap(),
-            ValidatorKind::Wordnik
-        );
-        assert_eq!(
-            "custom".parse::<ValidatorKind>().unwrap(),
-            ValidatorKind::Custom
-        );
-        assert!("unknown".parse::<ValidatorKind>().is_err());
+    fn throttle_delay(&self) -> Duration {
+        self.throttle
     }
 
-    #[test]
-    fn test_validator_kind_display_name() {
-        assert_eq!(
+    fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError> {
+        if !self.dictionary.contains(word) {
+            return Ok(None);
+        }
+        Ok(Some(WordEntry {
+            word: word.to_string(),
+            definition: OFFLINE_DEFINITION.to_string(),
+            definitions: vec![OFFLINE_DEFINITION.to_string()],
+            url: String::new(),
+            part_of_speech: None,
+        }))
+    }
+}
+
+/// Validator wrapper that memoizes lookups, including negative results.
+///
+/// Errors are not cached, so transient failures are retried on the next call.
+pub struct CachingValidator {
+    inner: Box<dyn Validator>,
+    name: String,
+    cache: Mutex<HashMap<String, Option<WordEntry>>>,
+    cache_file: Option<PathBuf>,
+    /// Earliest start of the next inner lookup, spaced by its throttle delay.
+    next_miss: Mutex<Instant>,
+}
+
+impl CachingValidator {
+    pub fn new(inner: Box<dyn Validator>) -> Self {
+        Self {
+            name: format!("{} (cached)", inner.name()),
+            inner,
+            cache: Mutex::new(HashMap::new()),
+            cache_file: None,
+            next_miss: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Back the cache with a JSON file: entries are loaded now if the file
+    /// exists, and written back by [`CachingValidator::save`] or on drop.
+    pub fn with_cache_file<P: Into<PathBuf>>(
+        inner: Box<dyn Validator>,
+        path: P,
+    ) -> Result<Self, SbsError> {
+        let path = path.into();
+        let mut validator = Self::new(inner);
+        if path.exists() {
+            let content = std::fs::read_to_string(&path)?;
+            let entries: HashMap<String, Option<WordEntry>> = serde_json::from_str(&content)
+                .map_err(|e| SbsError::SerializationError(e.to_string()))?;
+            validator.cache = Mutex::new(entries);
+        }
+        validator.cache_file = Some(path);
+        Ok(validator)
+    }
+
+    /// Write the cache to its backing file, if one was configured.
+    pub fn save(&self) -> Result<(), SbsError> {
+        let Some(path) = &self.cache_file else {
+            return Ok(());
+        };
+        let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        let content = serde_json::to_string(&*cache)
+            .map_err(|e| SbsError::SerializationError(e.to_string()))?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+impl Validator for CachingValidator {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Cache hits need no pause; [`CachingValidator::lookup`] applies the
+    /// inner validator's delay to cache misses only.
+    fn throttle_delay(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError> {
+        if let Some(cached) = self
+            .cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(word)
+        {
+            return Ok(cached.clone());
+        }
+
+        let start = {
+            let mut next = self.next_miss.lock().unwrap_or_else(|e| e.into_inner());
+            let start = (*next).max(Instant::now());
+            *next = start + self.inner.throttle_delay();
+            start
+        };
+        std::thread::sleep(start.saturating_duration_since(Instant::now()));
+        let result = self.inner.lookup(word)?;
+        self.cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(word.to_string(), result.clone());
+        Ok(result)
+    }
+}
+
+impl Drop for CachingValidator {
+    fn drop(&mut self) {
+        if let Err(e) = self.save() {
+            log::warn!("Failed to save validator cache: {}", e);
+        }
+    }
+}
+
+/// Fallback validator that consults its sources in order and returns the first
+/// hit. A source that errors is logged and treated as a miss.
+pub struct ChainValidator {
+    validators: Vec<Box<dyn Validator>>,
+    name: String,
+}
+
+impl ChainValidator {
+    pub fn new(validators: Vec<Box<dyn Validator>>) -> Self {
+        let names: Vec<&str> = validators.iter().map(|v| v.name()).collect();
+        let name = format!("Chain ({})", names.join(", "));
+        Self { validators, name }
+    }
+}
+
+impl Validator for ChainValidator {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The slowest source sets the pace, since any word may reach it.
+    fn throttle_delay(&self) -> Duration {
+        self.validators
+            .iter()
+            .map(|v| v.throttle_delay())
+            .max()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError> {
+        for validator in &self.validators {
+            match validator.lookup(word) {
+                Ok(Some(entry)) => return Ok(Some(entry)),
+                Ok(None) => {}
+                Err(e) => {
+                    log::warn!("{} failed for '{}': {}", validator.name(), word, e);
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Create a boxed validator from a kind, API key, and optional custom URL.
+pub fn create_validator(
+    kind: &ValidatorKind,
+    api_key: Option<&str>,
+    custom_url: Option<&str>,
+) -> Result<Box<dyn Validator>, SbsError> {
+    create_validator_with_timeout(kind, api_key, custom_url, DEFAULT_HTTP_TIMEOUT)
+}
+
+/// Like [`create_validator`], with an HTTP request timeout for online sources.
+pub fn create_validator_with_timeout(
+    kind: &ValidatorKind,
+    api_key: Option<&str>,
+    custom_url: Option<&str>,
+    timeout: Duration,
+) -> Result<Box<dyn Validator>, SbsError> {
+    match kind {
+        ValidatorKind::FreeDictionary => Ok(Box::new(
+            FreeDictionaryValidator::new()?.with_timeout(timeout)?,
+        )),
+        ValidatorKind::MerriamWebster => {
+            let key = api_key.ok_or_else(|| {
+                SbsError::ValidationError(
+                    "Merriam-Webster requires an API key (--api-key)".to_string(),
+                )
+            })?;
+            Ok(Box::new(
+                MerriamWebsterValidator::new(key)?.with_timeout(timeout)?,
+            ))
+        }
+        ValidatorKind::Wordnik => {
+            let key = api_key.ok_or_else(|| {
+                SbsError::ValidationError("Wordnik requires an API key (--api-key)".to_string())
+            })?;
+            Ok(Box::new(WordnikValidator::new(key)?.with_timeout(timeout)?))
+        }
+        ValidatorKind::Custom => {
+            let url = custom_url.ok_or_else(|| {
+                SbsError::ValidationError(
+                    "Custom validator requires a URL (--validator-url)".to_string(),
+                )
+            })?;
+            let validator = CustomValidator::new(url)?.with_timeout(timeout)?;
+            if !validator.probe()? {
+                return Err(SbsError::ValidationError(format!(
+                    "Custom URL '{}' does not appear to be a compatible dictionary API. \
+                     Expected Free Dictionary API-compatible JSON format.",
+                    url
+                )));
+            }
+            Ok(Box::new(validator))
+        }
+        ValidatorKind::Datamuse => Ok(Box::new(DatamuseValidator::new()?.with_timeout(timeout)?)),
+        ValidatorKind::Offline => {
+            let path = custom_url.ok_or_else(|| {
+                SbsError::ValidationError(
+                    "Offline validator requires a word list path (--validator-url)".to_string(),
+                )
+            })?;
+            Ok(Box::new(OfflineValidator::from_file(path)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_validator_kind_from_str() {
+        assert_eq!(
+            "free-dictionary".parse::<ValidatorKind>().unwrap(),
+            ValidatorKind::FreeDictionary
+        );
+        assert_eq!(
+            "merriam-webster".parse::<ValidatorKind>().unwrap(),
+            ValidatorKind::MerriamWebster
+        );
+        assert_eq!(
+            "wordnik".parse::<ValidatorKind>().unwrap(),
+            ValidatorKind::Wordnik
+        );
+        assert_eq!(
+            "custom".parse::<ValidatorKind>().unwrap(),
+            ValidatorKind::Custom
+        );
+        assert_eq!(
+            "offline".parse::<ValidatorKind>().unwrap(),
+            ValidatorKind::Offline
+        );
+        assert_eq!(
+            "datamuse".parse::<ValidatorKind>().unwrap(),
+            ValidatorKind::Datamuse
+        );
+        assert!("unknown".parse::<ValidatorKind>().is_err());
+    }
+
+    #[test]
+    fn test_validator_kind_from_str_aliases() {
+        let cases = [
+            ("Merriam-Webster", ValidatorKind::MerriamWebster),
+            ("mw", ValidatorKind::MerriamWebster),
+            ("MW", ValidatorKind::MerriamWebster),
+            ("freedictionary", ValidatorKind::FreeDictionary),
+            ("free", ValidatorKind::FreeDictionary),
+            ("DictAPI", ValidatorKind::FreeDictionary),
+            ("wn", ValidatorKind::Wordnik),
+            (" Offline ", ValidatorKind::Offline),
+            ("DATAMUSE", ValidatorKind::Datamuse),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                input.parse::<ValidatorKind>().unwrap(),
+                expected,
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_validator_kind_from_str_error_lists_canonical_names() {
+        let message = "webster".parse::<ValidatorKind>().unwrap_err().to_string();
+        assert!(message.contains("'webster'"));
+        assert!(message
+            .contains("free-dictionary, merriam-webster, wordnik, custom, offline, datamuse"));
+        assert!(!message.contains("mw"), "aliases are not advertised");
+    }
+
+    #[test]
+    fn test_validator_kind_display_name() {
+        assert_eq!(
             ValidatorKind::FreeDictionary.display_name(),
             "Free Dictionary"
         );
@@ -461,6 +1261,8 @@ mod tests {
         );
         assert_eq!(ValidatorKind::Wordnik.display_name(), "Wordnik");
         assert_eq!(ValidatorKind::Custom.display_name(), "Custom");
+        assert_eq!(ValidatorKind::Offline.display_name(), "Offline");
+        assert_eq!(ValidatorKind::Datamuse.display_name(), "Datamuse");
     }
 
     #[test]
@@ -469,6 +1271,8 @@ mod tests {
             word: "test".to_string(),
             definition: "A procedure for evaluation".to_string(),
             url: "https://example.com/test".to_string(),
+            definitions: vec![],
+            part_of_speech: None,
         };
         let json = serde_json::to_string(&entry).unwrap();
         assert!(json.contains("\"word\":\"test\""));
@@ -527,11 +1331,17 @@ mod tests {
                 word: "test".to_string(),
                 definition: "A trial".to_string(),
                 url: "https://example.com/test".to_string(),
+                definitions: vec![],
+                part_of_speech: None,
             }],
+            truncated: false,
+            unavailable: 2,
         };
         let json = serde_json::to_string(&summary).unwrap();
+        assert!(json.contains("\"unavailable\":2"));
         assert!(json.contains("\"candidates\":10"));
         assert!(json.contains("\"validated\":3"));
+        assert!(json.contains("\"truncated\":false"));
     }
 
     /// Mock validator for testing validate_words throttling and summary.
@@ -550,6 +1360,8 @@ mod tests {
                     word: word.to_string(),
                     definition: format!("Definition of {}", word),
                     url: format!("https://example.com/{}", word),
+                    definitions: vec![format!("Definition of {}", word)],
+                    part_of_speech: None,
                 }))
             } else {
                 Ok(None)
@@ -579,6 +1391,31 @@ mod tests {
         assert_eq!(summary.entries[1].word, "banana");
     }
 
+    #[test]
+    fn test_validate_words_streaming_emits_entries_before_progress() {
+        let validator = MockValidator {
+            known_words: vec!["apple".to_string(), "banana".to_string()],
+        };
+        let words = vec![
+            "apple".to_string(),
+            "xyzzy".to_string(),
+            "banana".to_string(),
+        ];
+        let events = Mutex::new(Vec::new());
+
+        let summary = validator.validate_words_streaming(
+            &words,
+            &|done, total| events.lock().unwrap().push(format!("{}/{}", done, total)),
+            &|entry| events.lock().unwrap().push(entry.word.clone()),
+        );
+
+        assert_eq!(
+            events.into_inner().unwrap(),
+            vec!["apple", "1/3", "2/3", "banana", "3/3"]
+        );
+        assert_eq!(summary.validated, 2);
+    }
+
     #[test]
     fn test_validate_words_empty_input() {
         let validator = MockValidator {
@@ -684,4 +1521,697 @@ mod tests {
         let arr = json_body.as_array().unwrap();
         assert!(arr.is_empty()); // Empty = not found
     }
+
+    #[test]
+    fn test_offline_validator_lookup() {
+        let validator = OfflineValidator::new(Dictionary::from_words(&["apple", "banana"]));
+
+        let entry = validator.lookup("Apple").unwrap().expect("apple is listed");
+        assert_eq!(entry.word, "Apple");
+        assert_eq!(entry.definition, "(offline: present in wordlist)");
+        assert!(entry.url.is_empty());
+
+        assert!(validator.lookup("app").unwrap().is_none(), "prefix only");
+        assert!(validator.lookup("xyzzy").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_create_validator_offline_requires_path() {
+        assert!(create_validator(&ValidatorKind::Offline, None, None).is_err());
+
+        let mut tmp = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut tmp, b"apple\n").unwrap();
+        let path = tmp.path().to_str().unwrap();
+        let v = create_validator(&ValidatorKind::Offline, None, Some(path)).unwrap();
+        assert_eq!(v.name(), "Offline");
+        assert!(v.lookup("apple").unwrap().is_some());
+    }
+
+    /// Mock validator that counts how often `lookup` reaches it.
+    struct CountingValidator {
+        inner: MockValidator,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl Validator for CountingValidator {
+        fn name(&self) -> &str {
+            self.inner.name()
+        }
+
+        fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.inner.lookup(word)
+        }
+    }
+
+    fn counting_validator(known: &[&str]) -> (Box<dyn Validator>, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let validator = CountingValidator {
+            inner: MockValidator {
+                known_words: known.iter().map(|w| w.to_string()).collect(),
+            },
+            calls: calls.clone(),
+        };
+        (Box::new(validator), calls)
+    }
+
+    #[test]
+    fn test_caching_validator_looks_up_each_word_once() {
+        let (inner, calls) = counting_validator(&["apple"]);
+        let validator = CachingValidator::new(inner);
+        assert_eq!(validator.name(), "Mock (cached)");
+
+        for _ in 0..3 {
+            assert!(validator.lookup("apple").unwrap().is_some());
+            assert!(validator.lookup("xyzzy").unwrap().is_none());
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_caching_validator_skips_throttle_for_cached_words() {
+        let validator = CachingValidator::new(Box::new(ThrottledMock {
+            throttle: Duration::from_millis(60),
+        }));
+        assert_eq!(validator.throttle_delay(), Duration::ZERO);
+        let words: Vec<String> = ["a", "b", "c"].map(String::from).to_vec();
+
+        let start = Instant::now();
+        validator.validate_words(&words);
+        assert!(
+            start.elapsed() >= Duration::from_millis(120),
+            "misses are still spaced by the inner delay"
+        );
+
+        let start = Instant::now();
+        validator.validate_words(&words);
+        assert!(
+            start.elapsed() < Duration::from_millis(60),
+            "cached words must not sleep"
+        );
+    }
+
+    #[test]
+    fn test_caching_validator_persists_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+
+        let (inner, _) = counting_validator(&["apple"]);
+        let validator = CachingValidator::with_cache_file(inner, &path).unwrap();
+        validator.lookup("apple").unwrap();
+        validator.lookup("xyzzy").unwrap();
+        drop(validator);
+
+        let (inner, calls) = counting_validator(&[]);
+        let validator = CachingValidator::with_cache_file(inner, &path).unwrap();
+        assert!(
+            validator.lookup("apple").unwrap().is_some(),
+            "served from file"
+        );
+        assert!(validator.lookup("xyzzy").unwrap().is_none());
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    /// Mock validator with a configurable throttle.
+    struct ThrottledMock {
+        throttle: Duration,
+    }
+
+    impl Validator for ThrottledMock {
+        fn name(&self) -> &str {
+            "Throttled"
+        }
+
+        fn throttle_delay(&self) -> Duration {
+            self.throttle
+        }
+
+        fn lookup(&self, _word: &str) -> Result<Option<WordEntry>, SbsError> {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn test_validate_words_uses_throttle_delay() {
+        let words = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+
+        let slow = ThrottledMock {
+            throttle: Duration::from_millis(60),
+        };
+        let start = std::time::Instant::now();
+        slow.validate_words(&words);
+        assert!(start.elapsed() >= Duration::from_millis(120), "two pauses");
+
+        let fast = ThrottledMock {
+            throttle: Duration::ZERO,
+        };
+        let start = std::time::Instant::now();
+        fast.validate_words(&words);
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_with_throttle_overrides_default() {
+        let v = FreeDictionaryValidator::new().unwrap();
+        assert_eq!(v.throttle_delay(), THROTTLE_DELAY);
+
+        let v = v.with_throttle(Duration::from_secs(1));
+        assert_eq!(v.throttle_delay(), Duration::from_secs(1));
+
+        let offline = OfflineValidator::new(Dictionary::new());
+        assert_eq!(offline.throttle_delay(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_with_timeout_builds_and_rejects_out_of_range() {
+        assert!(FreeDictionaryValidator::new()
+            .unwrap()
+            .with_timeout(Duration::from_secs(30))
+            .is_ok());
+        assert!(WordnikValidator::new("key")
+            .unwrap()
+            .with_timeout(Duration::from_secs(1))
+            .is_ok());
+
+        for timeout in [Duration::ZERO, Duration::from_secs(3600)] {
+            let result = DatamuseValidator::new().unwrap().with_timeout(timeout);
+            assert!(matches!(result, Err(SbsError::ValidationError(_))));
+        }
+        assert!(create_validator_with_timeout(
+            &ValidatorKind::FreeDictionary,
+            None,
+            None,
+            Duration::ZERO
+        )
+        .is_err());
+    }
+
+    /// Serve canned HTTP responses on a local port, one per connection.
+    /// Returns the base URL and a counter of requests received.
+    fn serve_responses(responses: Vec<(u16, &'static str)>) -> (String, Arc<AtomicUsize>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                counter.fetch_add(1, Ordering::SeqCst);
+                let reply = format!(
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(reply.as_bytes());
+            }
+        });
+
+        (base_url, hits)
+    }
+
+    /// Answer one request with a 404 and hand back its raw head (request
+    /// line and headers).
+    fn capture_request() -> (String, std::sync::mpsc::Receiver<String>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap_or(0);
+            let _ = sender.send(String::from_utf8_lossy(&buf[..n]).into_owned());
+            let _ = stream
+                .write_all(b"HTTP/1.1 404 X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+        });
+
+        (base_url, receiver)
+    }
+
+    #[test]
+    fn test_requests_send_user_agent_and_extra_headers() {
+        let (base_url, request) = capture_request();
+        let validator = FreeDictionaryValidator::with_base_url(&base_url)
+            .unwrap()
+            .with_header("Authorization", "Bearer secret-token")
+            .unwrap();
+
+        assert!(validator.lookup("hello").unwrap().is_none());
+
+        let head = request.recv().unwrap().to_lowercase();
+        assert!(
+            head.contains(&format!("user-agent: {}", USER_AGENT)),
+            "{}",
+            head
+        );
+        assert!(
+            head.contains("authorization: bearer secret-token"),
+            "{}",
+            head
+        );
+    }
+
+    #[test]
+    fn test_with_header_rejects_invalid_header() {
+        let result = FreeDictionaryValidator::new()
+            .unwrap()
+            .with_header("Bad Name", "value");
+        assert!(matches!(result, Err(SbsError::ValidationError(_))));
+        assert!(WordnikValidator::new("key\nwith newline").is_err());
+    }
+
+    #[test]
+    fn test_redact_url_hides_keys() {
+        assert_eq!(
+            redact_url("https://dictionaryapi.com/api/v3/x/json/bee?key=s3cret"),
+            "https://dictionaryapi.com/api/v3/x/json/bee?key=***"
+        );
+        assert_eq!(
+            redact_url("error sending request for url (http://h/w?limit=1&api_key=s3cret&x=1)"),
+            "error sending request for url (http://h/w?limit=1&api_key=***&x=1)"
+        );
+        assert_eq!(redact_url("http://h/w?monkey=1"), "http://h/w?monkey=1");
+    }
+
+    #[test]
+    fn test_wordnik_error_does_not_leak_api_key() {
+        // Bind and drop a listener so the port refuses connections.
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let validator = WordnikValidator::with_base_url("s3cret-key", &format!("http://{}", addr))
+            .unwrap()
+            .with_max_retries(0);
+
+        let message = validator.lookup("bee").unwrap_err().to_string();
+        assert!(message.contains("HTTP error"), "{}", message);
+        assert!(!message.contains("s3cret-key"), "{}", message);
+    }
+
+    #[test]
+    fn test_self_test_with_mock() {
+        let working = MockValidator {
+            known_words: vec![SELF_TEST_WORD.to_string()],
+        };
+        assert!(working.self_test().is_ok());
+
+        let wrong_endpoint = MockValidator {
+            known_words: Vec::new(),
+        };
+        let message = wrong_endpoint.self_test().unwrap_err().to_string();
+        assert!(
+            message.contains("Mock answered but does not know 'test'"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_self_test_reports_rejected_api_key() {
+        let (base_url, _) = serve_responses(vec![(401, "{}")]);
+        let validator = FreeDictionaryValidator::with_base_url(&base_url).unwrap();
+
+        let message = validator.self_test().unwrap_err().to_string();
+        assert!(
+            message.contains("Invalid or missing API key"),
+            "{}",
+            message
+        );
+        assert!(message.contains("401"), "{}", message);
+    }
+
+    #[test]
+    fn test_is_transient_status() {
+        for code in [429, 500, 502, 503, 504] {
+            assert!(is_transient_status(
+                reqwest::StatusCode::from_u16(code).unwrap()
+            ));
+        }
+        for code in [200, 400, 401, 404] {
+            assert!(!is_transient_status(
+                reqwest::StatusCode::from_u16(code).unwrap()
+            ));
+        }
+    }
+
+    #[test]
+    fn test_lookup_retries_transient_failures() {
+        let found = r#"[{"meanings":[{"definitions":[{"definition":"A greeting"}]}]}]"#;
+        let (base_url, hits) = serve_responses(vec![(503, "{}"), (429, "{}"), (200, found)]);
+
+        let validator = FreeDictionaryValidator::with_base_url(&base_url).unwrap();
+        let entry = validator
+            .lookup("hello")
+            .unwrap()
+            .expect("found after retries");
+
+        assert_eq!(entry.definition, "A greeting");
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_lookup_does_not_retry_not_found() {
+        let (base_url, hits) = serve_responses(vec![(404, "{}"), (200, "[]")]);
+
+        let validator = FreeDictionaryValidator::with_base_url(&base_url).unwrap();
+        assert!(validator.lookup("xyzzy").unwrap().is_none());
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_lookup_gives_up_after_max_retries() {
+        let (base_url, hits) = serve_responses(vec![(500, "{}"), (500, "{}")]);
+
+        let validator = FreeDictionaryValidator::with_base_url(&base_url)
+            .unwrap()
+            .with_max_retries(1);
+        assert!(validator.lookup("hello").is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_create_validator_datamuse() {
+        let v = create_validator(&ValidatorKind::Datamuse, None, None).unwrap();
+        assert_eq!(v.name(), "Datamuse");
+    }
+
+    #[test]
+    fn test_datamuse_parses_response() {
+        let json_body = serde_json::json!([
+            {"word": "hello", "score": 1000, "defs": ["n\tan expression of greeting", "v\tto greet"]},
+            {"word": "hallo", "score": 500, "defs": ["n\tvariant of hello"]}
+        ]);
+
+        assert_eq!(
+            parse_datamuse_definitions("hello", &json_body, 1),
+            Some((
+                vec!["an expression of greeting".to_string()],
+                Some("noun".to_string())
+            ))
+        );
+        let (definitions, _) = parse_datamuse_definitions("hello", &json_body, 5).unwrap();
+        assert_eq!(definitions, vec!["an expression of greeting", "to greet"]);
+    }
+
+    #[test]
+    fn test_datamuse_not_found_responses() {
+        let no_exact = serde_json::json!([{"word": "hallo", "defs": ["n\tvariant"]}]);
+        assert!(parse_datamuse_definitions("hello", &no_exact, 1).is_none());
+
+        let no_defs = serde_json::json!([{"word": "hello", "defs": []}]);
+        assert!(parse_datamuse_definitions("hello", &no_defs, 1).is_none());
+
+        let missing_defs = serde_json::json!([{"word": "hello", "score": 10}]);
+        assert!(parse_datamuse_definitions("hello", &missing_defs, 1).is_none());
+
+        assert!(parse_datamuse_definitions("hello", &serde_json::json!([]), 1).is_none());
+    }
+
+    #[test]
+    fn test_datamuse_lookup_uses_query_url() {
+        let body = r#"[{"word":"hello","defs":["n\tA greeting"]}]"#;
+        let (base_url, _) = serve_responses(vec![(200, body)]);
+
+        let validator = DatamuseValidator::with_base_url(&base_url).unwrap();
+        let entry = validator.lookup("hello").unwrap().unwrap();
+
+        assert_eq!(entry.definition, "A greeting");
+        assert_eq!(entry.url, format!("{}?sp=hello&md=d", base_url));
+    }
+
+    #[test]
+    fn test_word_entry_part_of_speech_backward_compatible() {
+        let json = r#"{"word":"test","definition":"A trial","url":"https://example.com"}"#;
+        let entry: WordEntry = serde_json::from_str(json).unwrap();
+        assert_eq!(entry.part_of_speech, None);
+    }
+
+    #[test]
+    fn test_lookup_captures_part_of_speech() {
+        let body = r#"[{"meanings":[{"partOfSpeech":"noun","definitions":[{"definition":"A greeting"}]}]}]"#;
+        let (base_url, _) = serve_responses(vec![(200, body)]);
+
+        let validator = FreeDictionaryValidator::with_base_url(&base_url).unwrap();
+        let entry = validator.lookup("hello").unwrap().unwrap();
+
+        assert_eq!(entry.part_of_speech.as_deref(), Some("noun"));
+    }
+
+    #[test]
+    fn test_lookup_collects_multiple_definitions() {
+        let body = r#"[{"meanings":[
+            {"partOfSpeech":"noun","definitions":[{"definition":"A greeting"},{"definition":"A call"}]},
+            {"partOfSpeech":"verb","definitions":[{"definition":"To greet"}]}
+        ]}]"#;
+        let (base_url, _) = serve_responses(vec![(200, body), (200, body)]);
+
+        let single = FreeDictionaryValidator::with_base_url(&base_url).unwrap();
+        let entry = single.lookup("hello").unwrap().unwrap();
+        assert_eq!(entry.definitions, vec!["A greeting"], "default is one");
+
+        let several = FreeDictionaryValidator::with_base_url(&base_url)
+            .unwrap()
+            .with_max_definitions(3);
+        let entry = several.lookup("hello").unwrap().unwrap();
+        assert_eq!(entry.definition, "A greeting");
+        assert_eq!(
+            entry.definitions,
+            vec!["A greeting", "A call", "To greet"],
+            "definitions span all meanings"
+        );
+    }
+
+    #[test]
+    fn test_first_definition_placeholder() {
+        assert_eq!(first_definition(&[]), NO_DEFINITION);
+        assert_eq!(first_definition(&["A".to_string()]), "A");
+    }
+
+    /// Validator that fails every lookup.
+    struct FailingValidator;
+
+    impl Validator for FailingValidator {
+        fn name(&self) -> &str {
+            "Failing"
+        }
+
+        fn lookup(&self, _word: &str) -> Result<Option<WordEntry>, SbsError> {
+            Err(SbsError::ValidationError("unavailable".to_string()))
+        }
+    }
+
+    /// Validator with one word per outcome: "found", "absent", and anything
+    /// else fails as if rate limited.
+    struct OutcomeMock;
+
+    impl Validator for OutcomeMock {
+        fn name(&self) -> &str {
+            "Outcome"
+        }
+
+        fn throttle_delay(&self) -> Duration {
+            Duration::ZERO
+        }
+
+        fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError> {
+            match word {
+                "found" => MockValidator {
+                    known_words: vec![word.to_string()],
+                }
+                .lookup(word),
+                "absent" => Ok(None),
+                _ => Err(SbsError::ValidationError("HTTP 429".to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_distinguishes_outcomes() {
+        let validator = OutcomeMock;
+        assert!(matches!(validator.check("found"), LookupOutcome::Found(e) if e.word == "found"));
+        assert!(matches!(validator.check("absent"), LookupOutcome::NotFound));
+        match validator.check("limited") {
+            LookupOutcome::Unavailable(reason) => assert!(reason.contains("429")),
+            other => panic!("expected Unavailable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_summary_counts_unavailable_separately() {
+        let words: Vec<String> = ["found", "absent", "limited", "throttled"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+
+        for summary in [
+            OutcomeMock.validate_words(&words),
+            OutcomeMock.validate_words_concurrent(&words, 3),
+        ] {
+            assert_eq!(summary.candidates, 4);
+            assert_eq!(summary.validated, 1);
+            assert_eq!(summary.unavailable, 2);
+            let absent = summary.candidates - summary.validated - summary.unavailable;
+            assert_eq!(absent, 1, "confirmed absent");
+        }
+    }
+
+    #[test]
+    fn test_chain_falls_through_to_next_validator() {
+        let chain = ChainValidator::new(vec![
+            Box::new(MockValidator {
+                known_words: vec!["apple".to_string()],
+            }),
+            Box::new(MockValidator {
+                known_words: vec!["apple".to_string(), "banana".to_string()],
+            }),
+        ]);
+
+        assert!(chain.lookup("apple").unwrap().is_some());
+        assert_eq!(
+            chain.lookup("banana").unwrap().map(|e| e.word),
+            Some("banana".to_string()),
+            "second validator supplies what the first lacks"
+        );
+        assert!(chain.lookup("xyzzy").unwrap().is_none());
+        assert_eq!(chain.name(), "Chain (Mock, Mock)");
+    }
+
+    #[test]
+    fn test_chain_treats_errors_as_misses() {
+        let chain = ChainValidator::new(vec![
+            Box::new(FailingValidator),
+            Box::new(MockValidator {
+                known_words: vec!["apple".to_string()],
+            }),
+        ]);
+        assert!(chain.lookup("apple").unwrap().is_some());
+        assert!(chain.lookup("xyzzy").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_chain_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ChainValidator>();
+    }
+
+    /// Validator whose lookups take a fixed time; accepts even-length words.
+    struct SlowMock {
+        latency: Duration,
+    }
+
+    impl Validator for SlowMock {
+        fn name(&self) -> &str {
+            "Slow"
+        }
+
+        fn throttle_delay(&self) -> Duration {
+            Duration::ZERO
+        }
+
+        fn lookup(&self, word: &str) -> Result<Option<WordEntry>, SbsError> {
+            std::thread::sleep(self.latency);
+            Ok(word.len().is_multiple_of(2).then(|| WordEntry {
+                word: word.to_string(),
+                definition: String::new(),
+                url: String::new(),
+                definitions: vec![],
+                part_of_speech: None,
+            }))
+        }
+    }
+
+    #[test]
+    fn test_validate_words_concurrent_matches_serial() {
+        let validator = SlowMock {
+            latency: Duration::from_millis(5),
+        };
+        let words: Vec<String> = ["ab", "abc", "abcd", "a", "abcdef", "xy", "xyz"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+
+        let serial = validator.validate_words(&words);
+        let concurrent = validator.validate_words_concurrent(&words, 3);
+
+        assert_eq!(concurrent.candidates, serial.candidates);
+        assert_eq!(concurrent.validated, serial.validated);
+        let order = |s: &ValidationSummary| -> Vec<String> {
+            s.entries.iter().map(|e| e.word.clone()).collect()
+        };
+        assert_eq!(order(&concurrent), order(&serial), "input order kept");
+    }
+
+    #[test]
+    fn test_validate_words_concurrent_overlaps_lookups() {
+        let validator = SlowMock {
+            latency: Duration::from_millis(50),
+        };
+        let words: Vec<String> = (0..8).map(|i| format!("w{}", i)).collect();
+
+        let start = Instant::now();
+        let summary = validator.validate_words_concurrent(&words, 8);
+        assert_eq!(summary.validated, 8);
+        assert!(
+            start.elapsed() < Duration::from_millis(300),
+            "lookups overlap"
+        );
+    }
+
+    #[test]
+    fn test_validate_words_concurrent_reports_progress() {
+        let validator = MockValidator {
+            known_words: vec!["apple".to_string()],
+        };
+        let words = vec!["apple".to_string(), "xyzzy".to_string(), "pear".to_string()];
+        let calls = Mutex::new(Vec::new());
+
+        validator.validate_words_concurrent_with_progress(&words, 2, &|done, total| {
+            calls.lock().unwrap().push((done, total));
+        });
+
+        let mut calls = calls.into_inner().unwrap();
+        calls.sort();
+        assert_eq!(calls, vec![(1, 3), (2, 3), (3, 3)]);
+    }
+
+    #[test]
+    fn test_validate_words_concurrent_empty() {
+        let validator = MockValidator {
+            known_words: vec![],
+        };
+        let summary = validator.validate_words_concurrent(&[], 4);
+        assert_eq!(summary.candidates, 0);
+        assert!(summary.entries.is_empty());
+    }
+
+    #[test]
+    fn test_validate_words_until_truncates_at_deadline() {
+        let validator = SlowMock {
+            latency: Duration::from_millis(40),
+        };
+        let words: Vec<String> = (0..20).map(|i| format!("w{}", i)).collect();
+
+        let start = Instant::now();
+        let summary = validator.validate_words_until(&words, start + Duration::from_millis(100));
+        assert!(start.elapsed() < Duration::from_millis(300));
+        assert!(summary.truncated);
+        assert_eq!(summary.candidates, 20);
+        assert!(summary.validated > 0 && summary.validated < 20);
+        assert_eq!(summary.entries[0].word, "w0", "keeps what was validated");
+
+        let summary =
+            validator.validate_words_until(&words[..2], Instant::now() + Duration::from_secs(5));
+        assert!(!summary.truncated);
+        assert_eq!(summary.validated, 2);
+    }
 }
@@ -1,18 +1,27 @@
 //! FFI bindings for Spelling Bee Solver.
 //!
 //! Provides a C-compatible interface for loading dictionaries and solving puzzles.
-//! Dictionary is managed as an opaque pointer (Box/unbox pattern). No global state.
+//! Dictionary is managed as an opaque, reference-counted pointer (`Arc`). No global state.
+//!
+//! A loaded dictionary is immutable, so one pointer may be shared by concurrent
+//! `sbs_solve` calls from multiple threads.
 //!
 //! # Memory Safety Contract
 //!
-//! - Pointers returned by `sbs_load_dictionary` must be freed with `sbs_free_dictionary`.
-//! - Pointers returned by `sbs_solve` must be freed with `sbs_free_string`.
+//! - Pointers returned by `sbs_load_dictionary`, `sbs_load_dictionary_from_buffer`
+//!   and `sbs_dictionary_clone` must each be freed with `sbs_free_dictionary`.
+//!   A clone is another reference to the same dictionary: freeing one reference
+//!   leaves the others valid, and the dictionary is released with the last one.
+//! - Pointers returned by `sbs_solve` and `sbs_lookup` must be freed with `sbs_free_string`.
 //! - The pointer from `sbs_version` is static and must NOT be freed.
+//! - Word pointers passed to an `sbs_solve_each` callback are only valid during that
+//!   call and must NOT be retained or freed.
 //! - No pointer may be used after it has been freed (use-after-free).
 //! - No pointer may be freed more than once (double-free), except null which is always safe.
 
-use sbs::{Config, Dictionary, Solver};
-use std::ffi::{c_char, CStr, CString};
+use sbs::{Config, Dictionary, SbsError, SolveResult, Solver};
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::sync::Arc;
 
 /// Static version string.
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -21,6 +30,65 @@ const VERSION: &str = env!("CARGO_PKG_VERSION");
 /// Guards against excessive memory allocation from untrusted input.
 const MAX_REQUEST_LEN: usize = 1024 * 1024;
 
+// `Dictionary` must stay shareable across threads for concurrent `sbs_solve` calls.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Dictionary>();
+};
+
+/// Status code: success.
+pub const SBS_OK: i32 = 0;
+/// Status code: a pointer argument was null.
+pub const SBS_ERR_NULL_ARG: i32 = 1;
+/// Status code: the request exceeded `MAX_REQUEST_LEN`.
+pub const SBS_ERR_TOO_LARGE: i32 = 2;
+/// Status code: the request was not valid UTF-8 JSON.
+pub const SBS_ERR_BAD_JSON: i32 = 3;
+/// Status code: the solver rejected the request.
+pub const SBS_ERR_SOLVE: i32 = 4;
+
+/// An FFI failure: a status code plus a descriptive message.
+struct FfiError {
+    code: i32,
+    message: String,
+    /// 1-based position of a JSON syntax or type error in the request.
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+impl FfiError {
+    fn new(code: i32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    /// A `SBS_ERR_BAD_JSON` error carrying the parse location, if known.
+    fn bad_json(error: SbsError) -> Self {
+        match error {
+            SbsError::ParseError {
+                message,
+                line,
+                column,
+            } => Self {
+                code: SBS_ERR_BAD_JSON,
+                message: format!("invalid JSON: {message}"),
+                line,
+                column,
+            },
+            other => Self::new(SBS_ERR_BAD_JSON, other.to_string()),
+        }
+    }
+}
+
+/// Hand a dictionary to the caller as one reference of an `Arc`.
+fn into_handle(dict: Dictionary) -> *mut Dictionary {
+    Arc::into_raw(Arc::new(dict)).cast_mut()
+}
+
 /// Load a dictionary from the given file path.
 ///
 /// Returns an opaque pointer to the Dictionary, or null on failure.
@@ -39,78 +107,273 @@ pub unsafe extern "C" fn sbs_load_dictionary(path: *const c_char) -> *mut Dictio
         Err(_) => return std::ptr::null_mut(),
     };
     match Dictionary::from_file(path_str) {
-        Ok(dict) => Box::into_raw(Box::new(dict)),
+        Ok(dict) => into_handle(dict),
         Err(_) => std::ptr::null_mut(),
     }
 }
 
-/// Free a Dictionary previously returned by `sbs_load_dictionary`.
+/// Load a dictionary from an in-memory buffer of newline-separated words.
+///
+/// Returns an opaque pointer to the Dictionary, or null if `data` is null or
+/// not valid UTF-8. The buffer is copied; the caller keeps ownership of it.
+/// The returned pointer must be freed with `sbs_free_dictionary`.
 ///
+/// # Safety
+/// `data` must point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sbs_load_dictionary_from_buffer(
+    data: *const u8,
+    len: usize,
+) -> *mut Dictionary {
+    if data.is_null() {
+        return std::ptr::null_mut();
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(data, len) };
+    let text = match std::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(_) => return std::ptr::null_mut(),
+    };
+    match Dictionary::from_reader(text.as_bytes()) {
+        Ok(dict) => into_handle(dict),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Free a dictionary reference returned by `sbs_load_dictionary`,
+/// `sbs_load_dictionary_from_buffer` or `sbs_dictionary_clone`.
+///
+/// The dictionary itself is released once every reference is freed.
 /// Passing null is a no-op.
 ///
 /// # Safety
-/// `ptr` must be a pointer returned by `sbs_load_dictionary`, or null.
-/// Must not be called more than once for the same pointer.
+/// `ptr` must be a pointer returned by a dictionary loader or
+/// `sbs_dictionary_clone`, or null. Each returned reference must be freed
+/// exactly once.
 #[no_mangle]
 pub unsafe extern "C" fn sbs_free_dictionary(ptr: *mut Dictionary) {
     if !ptr.is_null() {
         unsafe {
-            drop(Box::from_raw(ptr));
+            drop(Arc::from_raw(ptr.cast_const()));
         }
     }
 }
 
+/// Return a new reference to a loaded dictionary, for sharing it between
+/// independent owners. No words are copied.
+///
+/// The result must be freed with `sbs_free_dictionary`, independently of
+/// `dict`; freeing either leaves the other usable. The returned pointer may
+/// compare equal to `dict`. Returns null if `dict` is null.
+///
+/// # Safety
+/// `dict` must be a live pointer returned by a dictionary loader or
+/// `sbs_dictionary_clone`, or null.
+#[no_mangle]
+pub unsafe extern "C" fn sbs_dictionary_clone(dict: *const Dictionary) -> *mut Dictionary {
+    if dict.is_null() {
+        return std::ptr::null_mut();
+    }
+    unsafe { Arc::increment_strong_count(dict) };
+    dict.cast_mut()
+}
+
+/// Return the number of words in a dictionary, or -1 if `dict` is null.
+///
+/// # Safety
+/// `dict` must be a live pointer returned by a dictionary loader or
+/// `sbs_dictionary_clone`, or null.
+#[no_mangle]
+pub unsafe extern "C" fn sbs_dictionary_word_count(dict: *const Dictionary) -> i64 {
+    if dict.is_null() {
+        return -1;
+    }
+    let dict = unsafe { &*dict };
+    dict.word_count() as i64
+}
+
 /// Solve a puzzle given a dictionary and a JSON request string.
 ///
 /// The request JSON should have the shape: `{"letters": "abc", "present": "a"}`.
-/// Returns a JSON string: `{"words": [...]}` on success, or `{"error": "..."}` on failure.
+/// Returns a JSON string: `{"words": [...], "pangrams": [...], "count": N}` on
+/// success, or `{"error": "..."}` on failure.
 /// The caller must free the returned string with `sbs_free_string`.
 ///
 /// Input is limited to 1 MiB to prevent excessive memory allocation.
 ///
 /// # Safety
-/// - `dict` must be a valid pointer returned by `sbs_load_dictionary`.
+/// - `dict` must be a live pointer returned by a dictionary loader or
+///   `sbs_dictionary_clone`.
 /// - `request_json` must be a valid null-terminated UTF-8 string.
 #[no_mangle]
 pub unsafe extern "C" fn sbs_solve(
     dict: *const Dictionary,
     request_json: *const c_char,
 ) -> *mut c_char {
-    if dict.is_null() || request_json.is_null() {
-        return to_json_error("null pointer argument");
+    unsafe { sbs_solve_ex(dict, request_json, std::ptr::null_mut()) }
+}
+
+/// Like `sbs_solve`, but also writes a numeric status to `status` (if non-null):
+/// `SBS_OK` on success or one of the `SBS_ERR_*` codes. The JSON result is
+/// returned either way and must be freed with `sbs_free_string`.
+///
+/// # Safety
+/// - `dict` must be a live pointer returned by a dictionary loader or
+///   `sbs_dictionary_clone`.
+/// - `request_json` must be a valid null-terminated UTF-8 string.
+/// - `status` must be a valid pointer to an `i32`, or null.
+#[no_mangle]
+pub unsafe extern "C" fn sbs_solve_ex(
+    dict: *const Dictionary,
+    request_json: *const c_char,
+    status: *mut i32,
+) -> *mut c_char {
+    let (code, result) = match unsafe { solve_request(dict, request_json) } {
+        Ok(result) => (
+            SBS_OK,
+            to_c_string(&serde_json::to_string(&result).unwrap_or_default()),
+        ),
+        Err(e) => (e.code, to_json_error(&e)),
+    };
+    if !status.is_null() {
+        unsafe { *status = code };
     }
+    result
+}
 
-    let dict = unsafe { &*dict };
-    let c_str = unsafe { CStr::from_ptr(request_json) };
-    let json_bytes = c_str.to_bytes();
+/// Solve a puzzle and invoke `callback` once per matching word, in the requested sort order.
+///
+/// Each call receives the word as a null-terminated string and the caller's `user_data`.
+/// The word pointer is only valid for the duration of the callback: copy it if needed,
+/// and never free it. Returns the number of words, or a negated `SBS_ERR_*` code.
+///
+/// # Safety
+/// - `dict` must be a live pointer returned by a dictionary loader or
+///   `sbs_dictionary_clone`.
+/// - `request_json` must be a valid null-terminated UTF-8 string.
+/// - `callback` must be safe to call with the given `user_data`.
+#[no_mangle]
+pub unsafe extern "C" fn sbs_solve_each(
+    dict: *const Dictionary,
+    request_json: *const c_char,
+    callback: Option<extern "C" fn(*const c_char, *mut c_void)>,
+    user_data: *mut c_void,
+) -> i32 {
+    let Some(callback) = callback else {
+        return -SBS_ERR_NULL_ARG;
+    };
+    let result = match unsafe { solve_request(dict, request_json) } {
+        Ok(result) => result,
+        Err(e) => return -e.code,
+    };
 
-    if json_bytes.len() > MAX_REQUEST_LEN {
-        return to_json_error("request too large");
+    let mut count: i32 = 0;
+    for word in result.words {
+        // Dictionary words never contain interior nulls; skip defensively if one does.
+        if let Ok(c_word) = CString::new(word) {
+            callback(c_word.as_ptr(), user_data);
+            count = count.saturating_add(1);
+        }
     }
+    count
+}
 
-    let json_str = match c_str.to_str() {
-        Ok(s) => s,
-        Err(_) => return to_json_error("invalid UTF-8 in request"),
-    };
+/// Look up one word with a validator, e.g. to show a definition for a result.
+///
+/// Returns JSON `{"found": true, "word": ..., "definition": ..., "url": ...}`,
+/// `{"found": false}` when the source does not know the word, or
+/// `{"error": "..."}`. Free the result with `sbs_free_string`.
+///
+/// `validator_kind` is a validator name as accepted by the CLI (e.g.
+/// `free-dictionary`, `merriam-webster`, `offline`). `api_key` and
+/// `validator_url` (the custom endpoint, or the word list path for `offline`)
+/// may be null when the validator does not need them.
+///
+/// Online validators block on the network, for up to the HTTP timeout per
+/// attempt plus retries, so call this off the UI thread. Without the
+/// `validator` feature this always returns an error.
+///
+/// # Safety
+/// Each argument must be a valid null-terminated UTF-8 string or null.
+#[no_mangle]
+pub unsafe extern "C" fn sbs_lookup(
+    word: *const c_char,
+    validator_kind: *const c_char,
+    api_key: *const c_char,
+    validator_url: *const c_char,
+) -> *mut c_char {
+    let result = unsafe { lookup_request(word, validator_kind, api_key, validator_url) };
+    match result {
+        Ok(json) => to_c_string(&json.to_string()),
+        Err(e) => to_json_error(&e),
+    }
+}
 
-    let config: Config = match serde_json::from_str(json_str) {
-        Ok(c) => c,
-        Err(e) => return to_json_error(&format!("invalid JSON: {e}")),
+/// Validate the arguments of `sbs_lookup` and run the lookup.
+///
+/// # Safety
+/// Same contract as `sbs_lookup`.
+#[cfg(feature = "validator")]
+unsafe fn lookup_request(
+    word: *const c_char,
+    validator_kind: *const c_char,
+    api_key: *const c_char,
+    validator_url: *const c_char,
+) -> Result<serde_json::Value, FfiError> {
+    use sbs::{create_validator, LookupOutcome, ValidatorKind};
+
+    let (Some(word), Some(kind)) = (unsafe { optional_str(word) }?, unsafe {
+        optional_str(validator_kind)
+    }?) else {
+        return Err(FfiError::new(SBS_ERR_NULL_ARG, "null pointer argument"));
     };
+    let lookup_error = |e: SbsError| FfiError::new(SBS_ERR_SOLVE, e.to_string());
+    let kind: ValidatorKind = kind.parse().map_err(lookup_error)?;
+    let validator = create_validator(&kind, unsafe { optional_str(api_key) }?, unsafe {
+        optional_str(validator_url)
+    }?)
+    .map_err(lookup_error)?;
+
+    match validator.check(&word.trim().to_lowercase()) {
+        LookupOutcome::Found(entry) => Ok(serde_json::json!({
+            "found": true,
+            "word": entry.word,
+            "definition": entry.definition,
+            "url": entry.url,
+        })),
+        LookupOutcome::NotFound => Ok(serde_json::json!({ "found": false })),
+        LookupOutcome::Unavailable(reason) => Err(FfiError::new(SBS_ERR_SOLVE, reason)),
+    }
+}
 
-    let solver = Solver::new(config);
-    match solver.solve(dict) {
-        Ok(words) => {
-            let mut sorted: Vec<String> = words.into_iter().collect();
-            sorted.sort();
-            let result = serde_json::json!({ "words": sorted });
-            to_c_string(&result.to_string())
-        }
-        Err(e) => to_json_error(&e.to_string()),
+#[cfg(not(feature = "validator"))]
+unsafe fn lookup_request(
+    _word: *const c_char,
+    _validator_kind: *const c_char,
+    _api_key: *const c_char,
+    _validator_url: *const c_char,
+) -> Result<serde_json::Value, FfiError> {
+    Err(FfiError::new(
+        SBS_ERR_SOLVE,
+        "lookup unavailable: built without the validator feature",
+    ))
+}
+
+/// Decode an optional string argument; null maps to `None`.
+///
+/// # Safety
+/// `ptr` must be a valid null-terminated string or null.
+#[cfg(feature = "validator")]
+unsafe fn optional_str<'a>(ptr: *const c_char) -> Result<Option<&'a str>, FfiError> {
+    if ptr.is_null() {
+        return Ok(None);
     }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map(Some)
+        .map_err(|_| FfiError::new(SBS_ERR_BAD_JSON, "invalid UTF-8 in argument"))
 }
 
-/// Free a string previously returned by `sbs_solve`.
+/// Free a string previously returned by `sbs_solve` or `sbs_lookup`.
 ///
 /// Passing null is a no-op. Do NOT pass the pointer from `sbs_version` to this function.
 ///
@@ -137,8 +400,62 @@ pub extern "C" fn sbs_version() -> *const c_char {
         .as_ptr()
 }
 
-fn to_json_error(msg: &str) -> *mut c_char {
-    let result = serde_json::json!({ "error": msg });
+/// Return a static description of a status code from `sbs_solve_ex`.
+///
+/// The returned pointer is valid for the lifetime of the library and must NOT be freed.
+#[no_mangle]
+pub extern "C" fn sbs_error_message(code: i32) -> *const c_char {
+    let message = match code {
+        SBS_OK => c"ok",
+        SBS_ERR_NULL_ARG => c"null pointer argument",
+        SBS_ERR_TOO_LARGE => c"request too large",
+        SBS_ERR_BAD_JSON => c"invalid JSON request",
+        SBS_ERR_SOLVE => c"solve failed",
+        _ => c"unknown error",
+    };
+    message.as_ptr()
+}
+
+/// Validate the arguments, parse the request, and solve it.
+///
+/// # Safety
+/// Same contract as `sbs_solve`.
+unsafe fn solve_request(
+    dict: *const Dictionary,
+    request_json: *const c_char,
+) -> Result<SolveResult, FfiError> {
+    if dict.is_null() || request_json.is_null() {
+        return Err(FfiError::new(SBS_ERR_NULL_ARG, "null pointer argument"));
+    }
+    let dict = unsafe { &*dict };
+    let config = unsafe { parse_request(request_json) }?;
+    Solver::new(config)
+        .solve_result(dict)
+        .map_err(|e| FfiError::new(SBS_ERR_SOLVE, e.to_string()))
+}
+
+/// Decode and parse a JSON request, enforcing `MAX_REQUEST_LEN`.
+///
+/// # Safety
+/// `request_json` must be a valid, non-null, null-terminated string.
+unsafe fn parse_request(request_json: *const c_char) -> Result<Config, FfiError> {
+    let c_str = unsafe { CStr::from_ptr(request_json) };
+    if c_str.to_bytes().len() > MAX_REQUEST_LEN {
+        return Err(FfiError::new(SBS_ERR_TOO_LARGE, "request too large"));
+    }
+    let json_str = c_str
+        .to_str()
+        .map_err(|_| FfiError::new(SBS_ERR_BAD_JSON, "invalid UTF-8 in request"))?;
+    serde_json::from_str(json_str).map_err(|e| FfiError::bad_json(SbsError::from(e)))
+}
+
+/// `{"error": ...}`, plus `line` and `column` when the request failed to parse.
+fn to_json_error(error: &FfiError) -> *mut c_char {
+    let mut result = serde_json::json!({ "error": error.message });
+    if let (Some(line), Some(column)) = (error.line, error.column) {
+        result["line"] = line.into();
+        result["column"] = column.into();
+    }
     to_c_string(&result.to_string())
 }
 
@@ -228,6 +545,21 @@ mod tests {
         unsafe { sbs_free_dictionary(dict) };
     }
 
+    #[test]
+    fn test_solve_returns_shared_result_shape() {
+        let tmp = make_dict_file(&["apple", "plate", "pale"]);
+        let dict = load_dict(&tmp);
+        let parsed = solve_json(dict, r#"{"letters":"aelpt","present":"a"}"#);
+        let expected = SolveResult {
+            words: vec!["apple".into(), "pale".into(), "plate".into()],
+            pangrams: vec!["plate".into()],
+            count: 3,
+            bonus: Vec::new(),
+        };
+        assert_eq!(parsed, serde_json::to_value(expected).unwrap());
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
     #[test]
     fn test_load_and_free_dictionary() {
         let tmp = make_dict_file(&["hello", "world"]);
@@ -236,6 +568,307 @@ mod tests {
         // No crash = success
     }
 
+    // --- sbs_load_dictionary_from_buffer tests ---
+
+    #[test]
+    fn test_load_dictionary_from_buffer() {
+        let data = b"apple\nbanana\ncherry\n";
+        let dict = unsafe { sbs_load_dictionary_from_buffer(data.as_ptr(), data.len()) };
+        assert!(!dict.is_null());
+        assert_eq!(unsafe { sbs_dictionary_word_count(dict) }, 3);
+        let parsed = solve_json(dict, r#"{"letters":"aple","present":"a"}"#);
+        assert_eq!(parsed["words"], serde_json::json!(["apple"]));
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
+    #[test]
+    fn test_load_dictionary_from_empty_buffer() {
+        let data: &[u8] = b"";
+        let dict = unsafe { sbs_load_dictionary_from_buffer(data.as_ptr(), 0) };
+        assert!(!dict.is_null());
+        assert_eq!(unsafe { sbs_dictionary_word_count(dict) }, 0);
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
+    #[test]
+    fn test_load_dictionary_from_buffer_invalid_utf8() {
+        let data = b"apple\n\xff\xfe\n";
+        let dict = unsafe { sbs_load_dictionary_from_buffer(data.as_ptr(), data.len()) };
+        assert!(dict.is_null());
+    }
+
+    #[test]
+    fn test_load_dictionary_from_buffer_null() {
+        let dict = unsafe { sbs_load_dictionary_from_buffer(std::ptr::null(), 10) };
+        assert!(dict.is_null());
+    }
+
+    // --- sbs_solve_each tests ---
+
+    extern "C" fn collect_word(word: *const c_char, user_data: *mut c_void) {
+        let words = unsafe { &mut *(user_data as *mut Vec<String>) };
+        let word = unsafe { CStr::from_ptr(word) }.to_str().unwrap();
+        words.push(word.to_string());
+    }
+
+    #[test]
+    fn test_solve_each_invokes_callback_per_word() {
+        let tmp = make_dict_file(&["apple", "pale", "leap", "zebra"]);
+        let dict = load_dict(&tmp);
+        let req = CString::new(r#"{"letters":"aelp","present":"a"}"#).unwrap();
+        let mut words: Vec<String> = Vec::new();
+
+        let count = unsafe {
+            sbs_solve_each(
+                dict,
+                req.as_ptr(),
+                Some(collect_word),
+                &mut words as *mut Vec<String> as *mut c_void,
+            )
+        };
+
+        assert_eq!(count, 3);
+        assert_eq!(words, vec!["apple", "leap", "pale"]);
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
+    #[test]
+    fn test_solve_each_error_codes() {
+        let tmp = make_dict_file(&["apple"]);
+        let dict = load_dict(&tmp);
+        let valid = CString::new(r#"{"letters":"aelp","present":"a"}"#).unwrap();
+        let invalid = CString::new("not json").unwrap();
+        let bad_pattern =
+            CString::new(r#"{"letters":"aelp","present":"a","pattern":"("}"#).unwrap();
+        let mut words: Vec<String> = Vec::new();
+        let user_data = &mut words as *mut Vec<String> as *mut c_void;
+
+        unsafe {
+            assert_eq!(
+                sbs_solve_each(
+                    std::ptr::null(),
+                    valid.as_ptr(),
+                    Some(collect_word),
+                    user_data
+                ),
+                -SBS_ERR_NULL_ARG
+            );
+            assert_eq!(
+                sbs_solve_each(dict, valid.as_ptr(), None, user_data),
+                -SBS_ERR_NULL_ARG
+            );
+            assert_eq!(
+                sbs_solve_each(dict, invalid.as_ptr(), Some(collect_word), user_data),
+                -SBS_ERR_BAD_JSON
+            );
+            assert_eq!(
+                sbs_solve_each(dict, bad_pattern.as_ptr(), Some(collect_word), user_data),
+                -SBS_ERR_SOLVE
+            );
+            sbs_free_dictionary(dict);
+        }
+        assert!(words.is_empty());
+    }
+
+    // --- sbs_solve_ex / sbs_error_message tests ---
+
+    /// Helper: call sbs_solve_ex and return the status and parsed JSON.
+    fn solve_ex(dict: *const Dictionary, request: *const c_char) -> (i32, serde_json::Value) {
+        let mut status = -1;
+        let result = unsafe { sbs_solve_ex(dict, request, &mut status) };
+        assert!(!result.is_null());
+        let s = unsafe { CStr::from_ptr(result) }.to_str().unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(s).unwrap();
+        unsafe { sbs_free_string(result) };
+        (status, parsed)
+    }
+
+    #[test]
+    fn test_solve_ex_status_codes() {
+        let tmp = make_dict_file(&["apple"]);
+        let dict = load_dict(&tmp);
+        let valid = CString::new(r#"{"letters":"aelp","present":"a"}"#).unwrap();
+        let invalid = CString::new("not json").unwrap();
+        let too_large = CString::new("x".repeat(MAX_REQUEST_LEN + 1)).unwrap();
+        let bad_pattern =
+            CString::new(r#"{"letters":"aelp","present":"a","pattern":"("}"#).unwrap();
+
+        let (status, parsed) = solve_ex(dict, valid.as_ptr());
+        assert_eq!(status, SBS_OK);
+        assert_eq!(parsed["words"], serde_json::json!(["apple"]));
+
+        assert_eq!(
+            solve_ex(std::ptr::null(), valid.as_ptr()).0,
+            SBS_ERR_NULL_ARG
+        );
+        assert_eq!(solve_ex(dict, too_large.as_ptr()).0, SBS_ERR_TOO_LARGE);
+        assert_eq!(solve_ex(dict, invalid.as_ptr()).0, SBS_ERR_BAD_JSON);
+        let (status, parsed) = solve_ex(dict, bad_pattern.as_ptr());
+        assert!(
+            parsed.get("line").is_none(),
+            "only parse errors carry a location"
+        );
+        assert_eq!(status, SBS_ERR_SOLVE);
+        assert!(parsed["error"].is_string());
+
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
+    #[test]
+    fn test_solve_ex_reports_parse_location() {
+        let tmp = make_dict_file(&["apple"]);
+        let dict = load_dict(&tmp);
+        let malformed = CString::new("{\"letters\": \"aelp\",\n \"present\": }").unwrap();
+
+        let (status, parsed) = solve_ex(dict, malformed.as_ptr());
+        assert_eq!(status, SBS_ERR_BAD_JSON);
+        assert_eq!(parsed["line"], 2);
+        assert_eq!(parsed["column"], 13);
+        assert!(parsed["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid JSON:"));
+
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
+    #[test]
+    fn test_solve_ex_null_status_is_allowed() {
+        let tmp = make_dict_file(&["apple"]);
+        let dict = load_dict(&tmp);
+        let req = CString::new(r#"{"letters":"aelp","present":"a"}"#).unwrap();
+        let result = unsafe { sbs_solve_ex(dict, req.as_ptr(), std::ptr::null_mut()) };
+        assert!(!result.is_null());
+        unsafe {
+            sbs_free_string(result);
+            sbs_free_dictionary(dict);
+        }
+    }
+
+    #[test]
+    fn test_error_message() {
+        let message = |code| {
+            unsafe { CStr::from_ptr(sbs_error_message(code)) }
+                .to_str()
+                .unwrap()
+        };
+        assert_eq!(message(SBS_OK), "ok");
+        assert_eq!(message(SBS_ERR_TOO_LARGE), "request too large");
+        assert_eq!(message(SBS_ERR_SOLVE), "solve failed");
+        assert_eq!(message(99), "unknown error");
+        assert_eq!(
+            sbs_error_message(SBS_ERR_BAD_JSON),
+            sbs_error_message(SBS_ERR_BAD_JSON)
+        );
+    }
+
+    // --- concurrency tests ---
+
+    #[test]
+    fn test_concurrent_solve_shares_dictionary() {
+        let tmp = make_dict_file(&["apple", "pale", "leap", "bead", "cafe", "face", "dace"]);
+        let dict = load_dict(&tmp);
+        // Raw pointers are not Send; pass the address, as a C caller would.
+        let addr = dict as usize;
+
+        let requests = [
+            (
+                r#"{"letters":"aelp","present":"a"}"#,
+                vec!["apple", "leap", "pale"],
+            ),
+            (
+                r#"{"letters":"abcdef","present":"a"}"#,
+                vec!["bead", "cafe", "dace", "face"],
+            ),
+        ];
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let (request, expected) = &requests[i % requests.len()];
+                scope.spawn(move || {
+                    for _ in 0..50 {
+                        let parsed = solve_json(addr as *const Dictionary, request);
+                        assert_eq!(parsed["words"], serde_json::json!(expected));
+                    }
+                });
+            }
+        });
+
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
+    // --- sbs_dictionary_clone tests ---
+
+    #[test]
+    fn test_dictionary_clone_outlives_original() {
+        let tmp = make_dict_file(&["apple", "pale", "leap"]);
+        let dict = load_dict(&tmp);
+        let clone = unsafe { sbs_dictionary_clone(dict) };
+        assert!(!clone.is_null());
+
+        unsafe { sbs_free_dictionary(dict) };
+
+        let parsed = solve_json(clone, r#"{"letters":"aelp","present":"a"}"#);
+        assert_eq!(
+            parsed["words"],
+            serde_json::json!(["apple", "leap", "pale"])
+        );
+        assert_eq!(unsafe { sbs_dictionary_word_count(clone) }, 3);
+        unsafe { sbs_free_dictionary(clone) };
+    }
+
+    #[test]
+    fn test_dictionary_clone_shared_across_threads() {
+        let tmp = make_dict_file(&["apple", "pale", "leap"]);
+        let dict = load_dict(&tmp);
+        let clones: Vec<usize> = (0..4)
+            .map(|_| unsafe { sbs_dictionary_clone(dict) } as usize)
+            .collect();
+        unsafe { sbs_free_dictionary(dict) };
+
+        let handles: Vec<_> = clones
+            .into_iter()
+            .map(|ptr| {
+                std::thread::spawn(move || {
+                    let dict = ptr as *mut Dictionary;
+                    let parsed = solve_json(dict, r#"{"letters":"aelp","present":"a"}"#);
+                    unsafe { sbs_free_dictionary(dict) };
+                    parsed["words"].as_array().unwrap().len()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 3);
+        }
+    }
+
+    #[test]
+    fn test_dictionary_clone_null() {
+        assert!(unsafe { sbs_dictionary_clone(std::ptr::null()) }.is_null());
+    }
+
+    // --- sbs_dictionary_word_count tests ---
+
+    #[test]
+    fn test_dictionary_word_count() {
+        let tmp = make_dict_file(&["apple", "banana", "cherry"]);
+        let dict = load_dict(&tmp);
+        assert_eq!(unsafe { sbs_dictionary_word_count(dict) }, 3);
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
+    #[test]
+    fn test_dictionary_word_count_empty_file() {
+        let tmp = make_dict_file(&[]);
+        let dict = load_dict(&tmp);
+        assert_eq!(unsafe { sbs_dictionary_word_count(dict) }, 0);
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
+    #[test]
+    fn test_dictionary_word_count_null() {
+        assert_eq!(unsafe { sbs_dictionary_word_count(std::ptr::null()) }, -1);
+    }
+
     // --- sbs_free_dictionary tests ---
 
     #[test]
@@ -337,6 +970,21 @@ mod tests {
         unsafe { sbs_free_dictionary(dict) };
     }
 
+    #[test]
+    fn test_solve_empty_letters_is_an_error() {
+        let tmp = make_dict_file(&["apple"]);
+        let dict = load_dict(&tmp);
+
+        let parsed = solve_json(dict, r#"{"letters":"","present":"a"}"#);
+        let message = parsed["error"].as_str().unwrap();
+        assert!(message.contains("letters must not be empty"), "{}", message);
+
+        let parsed = solve_json(dict, r#"{"letters":"aelp","present":""}"#);
+        let message = parsed["error"].as_str().unwrap();
+        assert!(message.contains("present must not be empty"), "{}", message);
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
     #[test]
     fn test_solve_missing_present() {
         let tmp = make_dict_file(&["test"]);
@@ -370,6 +1018,74 @@ mod tests {
         unsafe { sbs_free_dictionary(dict) };
     }
 
+    #[test]
+    fn test_solve_case_sensitive_walrus_scenario() {
+        let tmp = make_dict_file(&["awls", "laws", "slaw", "wall", "walls", "walrus", "lure"]);
+        let dict = load_dict(&tmp);
+
+        // Uppercase W must start the word; lowercase l may appear anywhere
+        let parsed = solve_json(
+            dict,
+            r#"{"letters":"Walrus","present":"Wl","case-sensitive":true}"#,
+        );
+        let words: Vec<&str> = parsed["words"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect();
+        assert_eq!(words, ["wall", "walls", "walrus"]);
+
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
+    /// Helper: solve `request` against `dict` and return the words.
+    fn solve_words(dict: *const Dictionary, request: &str) -> Vec<String> {
+        let parsed = solve_json(dict, request);
+        serde_json::from_value(parsed["words"].clone()).expect("words array")
+    }
+
+    #[test]
+    fn test_solve_case_sensitive_start_only_letter() {
+        let tmp = make_dict_file(&["wear", "ware", "area", "rawer", "wawa"]);
+        let dict = load_dict(&tmp);
+
+        // Uppercase W may only start a word; lowercase letters go anywhere
+        let words = solve_words(
+            dict,
+            r#"{"letters":"Ware","present":"W","case-sensitive":true}"#,
+        );
+        assert_eq!(words, ["ware", "wear"]);
+
+        let words = solve_words(dict, r#"{"letters":"Ware","present":"W"}"#);
+        assert_eq!(
+            words,
+            ["rawer", "ware", "wawa", "wear"],
+            "case-insensitive by default"
+        );
+
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
+    #[test]
+    fn test_solve_repeats_policy() {
+        let tmp = make_dict_file(&["bead", "abba", "baaad", "dace"]);
+        let dict = load_dict(&tmp);
+        let solve = |repeats: &str| {
+            solve_words(
+                dict,
+                &format!(r#"{{"letters":"abcde","present":"a","repeats":{repeats}}}"#),
+            )
+        };
+
+        assert_eq!(solve("\"unlimited\""), ["abba", "baaad", "bead", "dace"]);
+        assert_eq!(solve("2"), ["abba", "bead", "dace"]);
+        assert_eq!(solve("\"once\""), ["bead", "dace"]);
+        assert_eq!(solve("0"), ["bead", "dace"], "0 means once");
+
+        unsafe { sbs_free_dictionary(dict) };
+    }
+
     #[test]
     fn test_solve_results_sorted() {
         let tmp = make_dict_file(&["zebra", "able", "fable", "bale", "label"]);
@@ -495,4 +1211,60 @@ mod tests {
             sbs_free_dictionary(dict);
         }
     }
+
+    // --- sbs_lookup tests ---
+
+    /// Helper: call sbs_lookup and return the parsed JSON value.
+    fn lookup_json(word: Option<&str>, kind: &str, url: Option<&str>) -> serde_json::Value {
+        let word = word.map(|w| CString::new(w).unwrap());
+        let kind = CString::new(kind).unwrap();
+        let url = url.map(|u| CString::new(u).unwrap());
+        let as_ptr = |s: &Option<CString>| s.as_ref().map_or(std::ptr::null(), |s| s.as_ptr());
+        let result =
+            unsafe { sbs_lookup(as_ptr(&word), kind.as_ptr(), std::ptr::null(), as_ptr(&url)) };
+        assert!(!result.is_null());
+        let parsed = serde_json::from_str(unsafe { CStr::from_ptr(result) }.to_str().unwrap());
+        unsafe { sbs_free_string(result) };
+        parsed.unwrap()
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn test_lookup_offline() {
+        let tmp = make_dict_file(&["apple", "leap"]);
+        let path = tmp.path().to_str().unwrap();
+
+        let found = lookup_json(Some("Apple"), "offline", Some(path));
+        assert_eq!(found["found"], true);
+        assert_eq!(found["word"], "apple");
+        assert!(found["definition"].is_string());
+
+        let missing = lookup_json(Some("zebra"), "offline", Some(path));
+        assert_eq!(missing, serde_json::json!({ "found": false }));
+
+        let no_path = lookup_json(Some("apple"), "offline", None);
+        assert!(no_path["error"]
+            .as_str()
+            .unwrap()
+            .contains("word list path"));
+
+        let unknown = lookup_json(Some("apple"), "thesaurus", None);
+        assert!(unknown["error"]
+            .as_str()
+            .unwrap()
+            .contains("Unknown validator"));
+
+        let null_word = lookup_json(None, "offline", Some(path));
+        assert_eq!(null_word["error"], "null pointer argument");
+    }
+
+    #[cfg(not(feature = "validator"))]
+    #[test]
+    fn test_lookup_without_validator_feature() {
+        let result = lookup_json(Some("apple"), "offline", None);
+        assert!(result["error"]
+            .as_str()
+            .unwrap()
+            .contains("without the validator feature"));
+    }
 }
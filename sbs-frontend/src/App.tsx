@@ -194,7 +194,8 @@ function App() {
         });
 
         if (!response.ok) {
-          throw new Error(await response.text() || 'Request failed');
+          const body = await response.json().catch(() => null);
+          throw new Error(body?.error?.message || 'Request failed');
         }
 
         const reader = response.body?.getReader();
@@ -218,15 +219,18 @@ function App() {
             if (data.progress) {
               setProgress(`Validating: ${data.progress.done} / ${data.progress.total}`);
             } else if (data.error) {
-              setError(data.error);
-            } else if (data.result) {
-              const result = data.result;
-              if (result.entries) {
-                const summary = result as ValidationSummary;
+              setError(data.error.message);
+            } else if (data.word) {
+              const entry = data.word as WordEntry;
+              setResults(prev => [...prev, entry]);
+            } else if (data.words) {
+              const words = data.words as string[];
+              setResults(prev => [...prev, ...words]);
+            } else if (data.done) {
+              if (data.summary) {
+                const summary = data.summary as ValidationSummary;
                 setCandidateCount(summary.candidates);
                 setResults(summary.entries);
-              } else {
-                setResults(result);
               }
             }
           }
@@ -243,7 +247,7 @@ function App() {
       // No validator — use the regular endpoint
       try {
         const response = await axios.post('/solve', payload);
-        setResults(response.data);
+        setResults(response.data.words);
       } catch (err: unknown) {
         const message = err instanceof Error ? err.message : 'Failed to connect to backend';
         console.error(err);
@@ -283,7 +287,7 @@ function App() {
     setDownloadOpen(false);
   };
 
-  const isValid = letters.length > 0;
+  const isValid = letters.length > 0 && present.length > 0;
 
   return (
     <div className="container">
@@ -11,7 +11,7 @@ describe('solveOnline', () => {
   it('sends correct payload', async () => {
     mockFetch.mockResolvedValue({
       ok: true,
-      json: async () => ['apple', 'ape'],
+      json: async () => ({words: ['apple', 'ape'], pangrams: [], count: 2}),
     });
 
     await solveOnline('http://localhost:8080', 'aple', 'a', 1);
@@ -25,10 +25,10 @@ describe('solveOnline', () => {
     );
   });
 
-  it('returns string array response', async () => {
+  it('returns the words of a solve result', async () => {
     mockFetch.mockResolvedValue({
       ok: true,
-      json: async () => ['apple', 'ape'],
+      json: async () => ({words: ['apple', 'ape'], pangrams: [], count: 2}),
     });
 
     const result = await solveOnline('http://localhost:8080', 'aple', 'a', null);
@@ -56,7 +56,7 @@ describe('solveOnline', () => {
   it('includes validator params in payload', async () => {
     mockFetch.mockResolvedValue({
       ok: true,
-      json: async () => [],
+      json: async () => ({words: [], pangrams: [], count: 0}),
     });
 
     await solveOnline('http://localhost:8080', 'abc', 'a', null, 'merriam-webster', 'mykey');
@@ -69,7 +69,7 @@ describe('solveOnline', () => {
   it('includes custom validator URL in payload', async () => {
     mockFetch.mockResolvedValue({
       ok: true,
-      json: async () => [],
+      json: async () => ({words: [], pangrams: [], count: 0}),
     });
 
     await solveOnline('http://localhost:8080', 'abc', 'a', null, 'custom', undefined, 'https://my-api.com');
@@ -82,7 +82,7 @@ describe('solveOnline', () => {
   it('strips trailing slash from backend URL', async () => {
     mockFetch.mockResolvedValue({
       ok: true,
-      json: async () => [],
+      json: async () => ({words: [], pangrams: [], count: 0}),
     });
 
     await solveOnline('http://localhost:8080///', 'abc', 'a', null);
@@ -3,7 +3,7 @@
 jest.mock('react-native', () => {
   const rn = jest.requireActual('react-native');
   rn.NativeModules.SbsSolver = {
-    solve: jest.fn().mockResolvedValue('{"words":["test"]}'),
+    solve: jest.fn().mockResolvedValue('{"words":["test"],"pangrams":[],"count":1}'),
     version: jest.fn().mockResolvedValue('0.1.0'),
   };
   return rn;
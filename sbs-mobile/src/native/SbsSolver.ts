@@ -4,6 +4,8 @@ const {SbsSolver} = NativeModules;
 
 export interface SolveResult {
   words: string[];
+  pangrams: string[];
+  count: number;
 }
 
 /**
@@ -10,7 +10,13 @@ export interface ValidationSummary {
   entries: WordEntry[];
 }
 
-export type SolveResponse = string[] | ValidationSummary;
+export interface SolveResult {
+  words: string[];
+  pangrams: string[];
+  count: number;
+}
+
+export type SolveResponse = SolveResult | ValidationSummary;
 
 function isValidationSummary(data: unknown): data is ValidationSummary {
   return (
@@ -89,5 +95,5 @@ export async function solveOnline(
     };
   }
 
-  return {results: data as string[], candidateCount: null};
+  return {results: (data as SolveResult).words, candidateCount: null};
 }